@@ -0,0 +1,76 @@
+//! Generates the module declarations and registry for every
+//! `src/yYYYY/dayNN.rs` file, so adding a day only requires adding its file.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    let src_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+
+    let years = matching_names(&src_dir, |name| {
+        let year = name.strip_prefix('y')?;
+        (year.len() == 4 && year.chars().all(|c| c.is_ascii_digit())).then(|| name.to_string())
+    });
+
+    let mut out = String::new();
+    let mut solutions = vec![];
+    for year in &years {
+        let year_dir = src_dir.join(year);
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+        let days = matching_names(&year_dir, |name| {
+            let module = name.strip_suffix(".rs")?;
+            let num = module.strip_prefix("day")?;
+            (num.len() == 2 && num.chars().all(|c| c.is_ascii_digit())).then(|| module.to_string())
+        });
+
+        writeln!(
+            out,
+            "/// Solutions to the {} event.\npub mod {} {{",
+            &year[1..],
+            year
+        )
+        .unwrap();
+        for module in &days {
+            let path = year_dir.join(format!("{}.rs", module));
+            writeln!(
+                out,
+                "    #[path = {:?}]\n    pub mod {};",
+                path.display().to_string(),
+                module
+            )
+            .unwrap();
+            solutions.push(format!("{}::{}::{}", year, module, struct_name(module)));
+        }
+        out.push_str("}\n\n");
+    }
+
+    out.push_str("/// Every solution found under `src`, in file order.\n");
+    out.push_str(
+        "pub(crate) fn registered_solutions() -> Vec<&'static dyn solution::DynSolution> {\n    vec![\n",
+    );
+    for solution in &solutions {
+        writeln!(out, "        &{},", solution).unwrap();
+    }
+    out.push_str("    ]\n}\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out_path, out).expect("failed to write days.rs");
+}
+
+/// Returns the sorted results of `f` for the names of the entries in `dir`
+/// that it accepts.
+fn matching_names(dir: &Path, f: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", dir.display(), err))
+        .filter_map(|entry| f(&entry.ok()?.file_name().into_string().ok()?))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Maps a module name like `day05` to the name of its solution struct, `Day05`.
+fn struct_name(module: &str) -> String {
+    let mut chars = module.chars();
+    let first = chars.next().unwrap().to_ascii_uppercase();
+    std::iter::once(first).chain(chars).collect()
+}
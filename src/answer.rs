@@ -0,0 +1,194 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use num::BigInt;
+use serde::{Serialize, Serializer};
+
+/// The answer to one part of a day's problem.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    #[serde(serialize_with = "serialize_display")]
+    Big(BigInt),
+    Text(String),
+    /// Rows of lit and unlit pixels that spell out the answer, which has to be
+    /// read off by eye.
+    #[serde(serialize_with = "serialize_rows")]
+    Grid(Vec<Vec<bool>>),
+    /// The part hasn't been solved yet.
+    Unsolved,
+}
+
+impl Answer {
+    /// Builds a grid just big enough to hold the lit pixels at `points`, given
+    /// as `(x, y)` pairs.
+    pub fn grid(points: impl IntoIterator<Item = (usize, usize)>) -> Answer {
+        let mut rows: Vec<Vec<bool>> = vec![];
+        for (x, y) in points {
+            if y >= rows.len() {
+                rows.resize(y + 1, vec![]);
+            }
+            let row = &mut rows[y];
+            if x >= row.len() {
+                row.resize(x + 1, false);
+            }
+            row[x] = true;
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, false);
+        }
+        Answer::Grid(rows)
+    }
+
+    /// The answer as it should be submitted to the website. Grids have to be
+    /// read and submitted by hand, and unsolved parts have nothing to submit.
+    pub fn submission(&self) -> Result<String> {
+        match self {
+            Answer::Grid(_) => Err(anyhow!(
+                "the answer is a grid of pixels, read it and pass the letters as the answer:\n{}",
+                self
+            )),
+            Answer::Unsolved => Err(anyhow!("the part isn't solved yet")),
+            _ => Ok(self.to_string()),
+        }
+    }
+
+    /// Whether the answer is the same as `expected`, a previously recorded or
+    /// given answer. Grids are compared by how they're displayed, and an
+    /// unsolved part never matches.
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Unsolved => false,
+            _ => *self == expected.trim_end_matches('\n'),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Grid(rows) => write!(f, "{}", render_rows(rows).join("\n")),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Compares by how the answer is displayed, mainly for tests.
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        let s = self.to_string();
+        s == *other
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $as:ty, $($from:ty),*) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(n: $from) -> Answer {
+                    Answer::$variant(n as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Signed, i64, i32, i64, isize);
+impl_from!(Unsigned, u64, u32, u64, usize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Answer {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+fn render_rows(rows: &[Vec<bool>]) -> Vec<String> {
+    rows.iter()
+        .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+        .collect()
+}
+
+fn serialize_display<S: Serializer>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(n)
+}
+
+fn serialize_rows<S: Serializer>(rows: &[Vec<bool>], serializer: S) -> Result<S::Ok, S::Error> {
+    render_rows(rows).serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+    use serde_json::json;
+
+    use super::Answer;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(7usize).to_string(), "7");
+        assert_eq!(
+            Answer::from(BigInt::from(u64::MAX) * 2).to_string(),
+            "36893488147419103230"
+        );
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(
+            Answer::grid([(0, 0), (2, 0), (1, 1)]).to_string(),
+            "#.#\n.#."
+        );
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+
+    #[test]
+    fn test_submission() {
+        assert_eq!(Answer::from(42u32).submission().unwrap(), "42");
+        assert_eq!(Answer::from("EPUELPBR").submission().unwrap(), "EPUELPBR");
+        assert!(Answer::grid([(0, 0)]).submission().is_err());
+        assert!(Answer::Unsolved.submission().is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(Answer::from(42u32).matches("42"));
+        assert!(!Answer::from(42u32).matches("43"));
+        assert!(Answer::grid([(1, 0)]).matches(".#\n"));
+        assert!(!Answer::Unsolved.matches("unsolved"));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let value = |answer: Answer| serde_json::to_value(answer).unwrap();
+        assert_eq!(
+            value(Answer::from(-3)),
+            json!({"kind": "signed", "value": -3})
+        );
+        assert_eq!(
+            value(Answer::from(BigInt::from(10).pow(20))),
+            json!({"kind": "big", "value": "100000000000000000000"})
+        );
+        assert_eq!(
+            value(Answer::grid([(0, 0), (1, 1)])),
+            json!({"kind": "grid", "value": ["#.", ".#"]})
+        );
+        assert_eq!(value(Answer::Unsolved), json!({"kind": "unsolved"}));
+    }
+}
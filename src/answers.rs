@@ -0,0 +1,123 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    cache::{write_atomic, Cache},
+    input::get_input,
+};
+
+/// The name of the store in each year's cache.
+pub const FILE: &str = "answers.json";
+
+/// Answers by day, then part, then hash of the input they were computed from.
+type Answers = BTreeMap<u32, BTreeMap<u32, BTreeMap<String, String>>>;
+
+/// Known-correct answers, persisted as JSON so that old days can be checked
+/// for regressions.
+pub struct AnswerStore {
+    path: PathBuf,
+    answers: Answers,
+}
+
+impl AnswerStore {
+    /// Loads the store at `path`, starting empty if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<AnswerStore> {
+        let answers = if path.exists() {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            serde_json::from_str(&text)
+                .with_context(|| format!("failed to parse {}", path.display()))?
+        } else {
+            Answers::new()
+        };
+        Ok(AnswerStore {
+            path: path.to_path_buf(),
+            answers,
+        })
+    }
+
+    /// Loads the store kept in the cache.
+    pub fn open(cache: &Cache) -> Result<AnswerStore> {
+        AnswerStore::load(&cache.path(FILE)?)
+    }
+
+    /// The recorded answer for one part with the input whose hash is given.
+    pub fn get(&self, day: u32, part: u32, input_hash: &str) -> Option<&str> {
+        self.answers
+            .get(&day)?
+            .get(&part)?
+            .get(input_hash)
+            .map(String::as_str)
+    }
+
+    /// Records an answer, returning the answer it replaced, if any.
+    pub fn record(
+        &mut self,
+        day: u32,
+        part: u32,
+        input_hash: &str,
+        answer: &str,
+    ) -> Option<String> {
+        self.answers
+            .entry(day)
+            .or_default()
+            .entry(part)
+            .or_default()
+            .insert(input_hash.to_string(), answer.to_string())
+    }
+
+    /// Writes the store back to where it was loaded from.
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = serde_json::to_string_pretty(&self.answers)?;
+        write_atomic(&self.path, &(text + "\n"))
+    }
+}
+
+/// Records an answer for the day's current puzzle input, returning the
+/// previously recorded answer, if any.
+pub fn record(cache: &Cache, day: u32, part: u32, answer: &str) -> Result<Option<String>> {
+    let input = get_input(cache, day).context("failed to load input")?;
+    let mut store = AnswerStore::open(cache)?;
+    let old = store.record(day, part, &input_hash(&input), answer);
+    store.save()?;
+    Ok(old)
+}
+
+/// Identifies an input by the hex-encoded SHA-256 hash of its contents.
+pub fn input_hash(input: &str) -> String {
+    hex::encode(Sha256::digest(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{input_hash, AnswerStore};
+
+    #[test]
+    fn test_record_and_reload() {
+        let path = env::temp_dir().join(format!("aoc-answers-{}.json", process::id()));
+        let hash = input_hash("1\n2\n3\n");
+
+        let mut store = AnswerStore::load(&path).unwrap();
+        assert_eq!(store.get(1, 1, &hash), None);
+        assert_eq!(store.record(1, 1, &hash, "2"), None);
+        assert_eq!(store.record(1, 1, &hash, "3"), Some("2".to_string()));
+        store.save().unwrap();
+
+        let store = AnswerStore::load(&path).unwrap();
+        assert_eq!(store.get(1, 1, &hash), Some("3"));
+        assert_eq!(store.get(1, 2, &hash), None);
+        assert_eq!(store.get(1, 1, &input_hash("other")), None);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    memory::{self, AllocStats},
+    solution::{DynSolution, Registry},
+};
+
+/// Changes smaller than this are treated as noise rather than regressions,
+/// since the fastest phases take well under a microsecond.
+const MIN_REGRESSION: Duration = Duration::from_micros(1);
+
+/// Summary statistics over a set of timings, in nanoseconds.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    /// The allocations made by a single iteration, if they're being counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+impl Stats {
+    /// Summarizes the time each iteration took. There must be at least one.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        let mut nanos: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        nanos.sort_unstable();
+        let len = nanos.len();
+        let median_ns = if len % 2 == 1 {
+            nanos[len / 2]
+        } else {
+            (nanos[len / 2 - 1] + nanos[len / 2]) / 2
+        };
+        // Nearest-rank percentile.
+        let p95_index = ((len as f64 * 0.95).ceil() as usize).max(1) - 1;
+        Stats {
+            min_ns: nanos[0],
+            median_ns,
+            mean_ns: nanos.iter().sum::<u64>() / len as u64,
+            p95_ns: nanos[p95_index],
+            alloc: None,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}  p95 {:>10.2?}",
+            Duration::from_nanos(self.min_ns),
+            Duration::from_nanos(self.median_ns),
+            Duration::from_nanos(self.mean_ns),
+            Duration::from_nanos(self.p95_ns),
+        )?;
+        match self.alloc {
+            Some(alloc) => write!(f, "  {}", alloc),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Timings for each phase of a single day.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DayStats {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayStats {
+    fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+}
+
+/// Benchmark results by day, as saved to and loaded from a baseline file.
+pub type Baseline = BTreeMap<u32, DayStats>;
+
+pub struct Options {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+/// Runs `warmup` untimed iterations of parsing and solving both parts, then
+/// `iterations` timed ones. Allocations are counted on the last iteration,
+/// when built with the `alloc-stats` feature.
+pub fn bench_day(solution: &dyn DynSolution, input: &str, options: &Options) -> Result<DayStats> {
+    for _ in 0..options.warmup {
+        let parsed = solution.parse(input)?;
+        solution.part1(&parsed)?;
+        solution.part2(&parsed)?;
+    }
+
+    let mut samples = [vec![], vec![], vec![]];
+    let mut allocs = [None; 3];
+    for _ in 0..options.iterations.max(1) {
+        let ((parsed, elapsed), alloc) = memory::measure(|| timed(|| solution.parse(input)));
+        samples[0].push(elapsed);
+        allocs[0] = alloc;
+        let parsed = parsed?;
+
+        for part in [1, 2] {
+            let ((answer, elapsed), alloc) =
+                memory::measure(|| timed(|| solution.solve(&parsed, part)));
+            answer?;
+            samples[part as usize].push(elapsed);
+            allocs[part as usize] = alloc;
+        }
+    }
+    let stats = |phase: usize| Stats {
+        alloc: allocs[phase],
+        ..Stats::from_samples(&samples[phase])
+    };
+    Ok(DayStats {
+        parse: stats(0),
+        part1: stats(1),
+        part2: stats(2),
+    })
+}
+
+/// Benchmarks each of `days` with the input `load_input` returns for it.
+pub fn bench_days(
+    registry: &Registry,
+    days: &[u32],
+    load_input: impl Fn(u32) -> Result<String>,
+    options: &Options,
+) -> Result<Baseline> {
+    let mut results = Baseline::new();
+    for &day in days {
+        let solution = registry.lookup(day)?;
+        let input = load_input(day).context("failed to load input")?;
+        let stats =
+            bench_day(solution, &input, options).with_context(|| format!("day {} failed", day))?;
+        results.insert(day, stats);
+    }
+    Ok(results)
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let val = f();
+    (val, start.elapsed())
+}
+
+/// Formats a table of the timings of each day and phase.
+pub fn report(results: &Baseline) -> String {
+    let mut out = String::new();
+    for (day, stats) in results {
+        for (phase, s) in stats.phases() {
+            writeln!(out, "Day {:>2} {:<5}  {}", day, phase, s).unwrap();
+        }
+    }
+    out.trim_end().to_string()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u32,
+    pub phase: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {:>2} {:<5}  median {:.2?} -> {:.2?} ({:+.1}%)",
+            self.day,
+            self.phase,
+            self.before,
+            self.after,
+            (self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0
+        )
+    }
+}
+
+/// Finds phases whose median time grew by more than `threshold` (a fraction,
+/// e.g. 0.1 for 10%) relative to the baseline. Days missing from the baseline
+/// are ignored.
+pub fn regressions(current: &Baseline, baseline: &Baseline, threshold: f64) -> Vec<Regression> {
+    let mut res = vec![];
+    for (&day, stats) in current {
+        let old_stats = match baseline.get(&day) {
+            Some(s) => s,
+            None => continue,
+        };
+        for ((phase, new), (_, old)) in stats.phases().into_iter().zip(old_stats.phases()) {
+            let before = Duration::from_nanos(old.median_ns);
+            let after = Duration::from_nanos(new.median_ns);
+            if after > before.mul_f64(1.0 + threshold) && after - before >= MIN_REGRESSION {
+                res.push(Regression {
+                    day,
+                    phase,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+    res
+}
+
+/// Reads results saved with `save_baseline`.
+pub fn load_baseline(path: &Path) -> Result<Baseline> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// Writes the results as JSON, to compare later runs against.
+pub fn save_baseline(path: &Path, results: &Baseline) -> Result<()> {
+    let text = serde_json::to_string_pretty(results)?;
+    fs::write(path, text + "\n").with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bench_day, regressions, Baseline, DayStats, Options, Stats};
+    use crate::{memory, solution::Registry};
+
+    fn stats(median_ns: u64) -> Stats {
+        Stats {
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            p95_ns: median_ns,
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<_> = (1..=20).rev().map(Duration::from_nanos).collect();
+        assert_eq!(
+            Stats::from_samples(&samples),
+            Stats {
+                min_ns: 1,
+                median_ns: 10,
+                mean_ns: 10,
+                p95_ns: 19,
+                alloc: None,
+            }
+        );
+        assert_eq!(Stats::from_samples(&[Duration::from_nanos(7)]), stats(7));
+    }
+
+    #[test]
+    fn test_regressions() {
+        let day = |parse, part1, part2| DayStats {
+            parse: stats(parse),
+            part1: stats(part1),
+            part2: stats(part2),
+        };
+        let baseline = Baseline::from([(1, day(100, 10_000, 10_000))]);
+        let current = Baseline::from([
+            // Parse regressed relatively but by less than the noise floor,
+            // part 1 is within the threshold and part 2 regressed.
+            (1, day(500, 10_500, 20_000)),
+            (2, day(100, 100, 100)),
+        ]);
+        let found = regressions(&current, &baseline, 0.1);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].day, 1);
+        assert_eq!(found[0].phase, "part2");
+        assert_eq!(found[0].after, Duration::from_nanos(20_000));
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let solution = Registry::new(2021).lookup(1).unwrap();
+        let options = Options {
+            warmup: 1,
+            iterations: 3,
+        };
+        let stats = bench_day(solution, "1\n3\n2\n4\n", &options).unwrap();
+        assert_eq!(stats.parse.alloc.is_some(), memory::ENABLED);
+        // Older baselines don't have allocation counts.
+        let json = r#"{"min_ns": 1, "median_ns": 2, "mean_ns": 3, "p95_ns": 4}"#;
+        assert_eq!(serde_json::from_str::<Stats>(json).unwrap().alloc, None);
+    }
+}
@@ -0,0 +1,401 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::SystemTime,
+};
+
+use anyhow::{anyhow, Context, Result};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::{
+    answers,
+    config::{Config, FILE, SESSION_ENV_VAR_NAME},
+};
+
+/// Files that hold state rather than downloads, and so survive `clear`.
+const KEPT_FILES: &[&str] = &[answers::FILE];
+
+/// Where downloaded inputs, puzzles and examples are kept.
+///
+/// Everything lives under `<root>/<year>/<account>`, where the account is
+/// identified by a hash of the session cookie, since inputs differ by account.
+/// The root is `$AOC_CACHE_DIR` or `cache_dir` from the config if set, else
+/// `$XDG_CACHE_HOME/aoc`, else `~/.cache/aoc`.
+///
+/// Without a session cookie the account is the configured `account`, or else
+/// the only one cached for the year, so that cached files can still be read
+/// offline. That's worked out when the cache is first used rather than up
+/// front.
+pub struct Cache {
+    year: i32,
+    /// `<root>/<year>`, holding a directory per account.
+    year_dir: PathBuf,
+    /// The account's directory name, or `None` if nothing has been cached for
+    /// any account yet. An error says why the account is ambiguous.
+    account: OnceLock<Result<Option<String>, String>>,
+}
+
+impl Cache {
+    /// The cache for `year` with the configured root and account.
+    pub fn from_env(year: i32) -> Result<Cache> {
+        Cache::from_config(Config::shared()?, year)
+    }
+
+    /// The cache for `year` with `config`'s root and account. Without a
+    /// session cookie configured, the account is the configured one, if any,
+    /// so that this works offline; a session file that can't be read is an
+    /// error rather than a reason to fall back to it.
+    pub fn from_config(config: &Config, year: i32) -> Result<Cache> {
+        let root = config.cache_dir()?;
+        if config.session.is_none() && config.session_file.is_none() {
+            return Ok(Cache::for_account(&root, year, config.account.clone()));
+        }
+        Ok(Cache::new(&root, year, &config.session()?))
+    }
+
+    /// The cache for the account that `session` belongs to.
+    pub fn new(root: &Path, year: i32, session: &str) -> Cache {
+        let account = hex::encode(Sha256::digest(session.as_bytes()))[..12].to_string();
+        Cache::for_account(root, year, Some(account))
+    }
+
+    /// The cache for the named account directory, or if `None`, for the only
+    /// account with anything cached for `year`.
+    pub fn for_account(root: &Path, year: i32, account: Option<String>) -> Cache {
+        let cell = OnceLock::new();
+        if let Some(account) = account {
+            cell.set(Ok(Some(account))).unwrap();
+        }
+        Cache {
+            year,
+            year_dir: root.join(year.to_string()),
+            account: cell,
+        }
+    }
+
+    /// The year whose files this cache holds.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The account's directory, which fails if it can't be told which account
+    /// to use.
+    pub fn dir(&self) -> Result<PathBuf> {
+        self.existing_dir()?.ok_or_else(|| {
+            anyhow!(
+                "nothing is cached in {} yet, and there's no session cookie to download \
+                 with, set ${} or add `session` or `session_file` to {}",
+                self.year_dir.display(),
+                SESSION_ENV_VAR_NAME,
+                FILE
+            )
+        })
+    }
+
+    /// The path of a file in the account's directory.
+    pub fn path(&self, name: &str) -> Result<PathBuf> {
+        Ok(self.dir()?.join(name))
+    }
+
+    /// The account's directory, or `None` if there's no session cookie and
+    /// nothing cached for any account yet, so there's nothing to read.
+    fn existing_dir(&self) -> Result<Option<PathBuf>> {
+        let account = self.account.get_or_init(|| self.only_account());
+        match account {
+            Ok(account) => Ok(account.as_ref().map(|account| self.year_dir.join(account))),
+            Err(err) => Err(anyhow!("{}", err)),
+        }
+    }
+
+    /// Finds the only account with a directory for the year.
+    fn only_account(&self) -> Result<Option<String>, String> {
+        let entries = match fs::read_dir(&self.year_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(format!(
+                    "failed to read {}: {}",
+                    self.year_dir.display(),
+                    err
+                ))
+            }
+        };
+        let mut accounts = vec![];
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                accounts.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        match accounts.len() {
+            0 | 1 => Ok(accounts.pop()),
+            n => Err(format!(
+                "{} accounts are cached in {} and there's no session cookie to pick one, \
+                 set `account` in {} to one of them",
+                n,
+                self.year_dir.display(),
+                FILE
+            )),
+        }
+    }
+
+    /// Reads a cached file, returning `None` if it doesn't exist.
+    pub fn read(&self, name: &str) -> Result<Option<String>> {
+        let path = match self.existing_dir()? {
+            Some(dir) => dir.join(name),
+            None => {
+                debug!(name, "cache miss");
+                return Ok(None);
+            }
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                debug!(name, "cache hit");
+                Ok(Some(text))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                debug!(name, "cache miss");
+                Ok(None)
+            }
+            Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    /// When a cached file was last written, or `None` if it doesn't exist.
+    pub fn modified(&self, name: &str) -> Result<Option<SystemTime>> {
+        let path = match self.existing_dir()? {
+            Some(dir) => dir.join(name),
+            None => return Ok(None),
+        };
+        match fs::metadata(&path) {
+            Ok(metadata) => Ok(Some(metadata.modified()?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    /// Checks that `contents` is valid for the named file, then writes it.
+    pub fn write(&self, name: &str, contents: &str) -> Result<()> {
+        validate(name, contents).with_context(|| format!("refusing to cache {}", name))?;
+        let dir = self.dir()?;
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        write_atomic(&dir.join(name), contents)
+    }
+
+    /// Lists the cached files and their sizes in bytes, by name.
+    pub fn list(&self) -> Result<Vec<(String, u64)>> {
+        let dir = match self.existing_dir()? {
+            Some(dir) => dir,
+            None => return Ok(vec![]),
+        };
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", dir.display()))
+            }
+        };
+        let mut files = vec![];
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                files.push((name, entry.metadata()?.len()));
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Checks every cached file, returning the names of the invalid ones along
+    /// with what's wrong with them.
+    pub fn verify(&self) -> Result<Vec<(String, String)>> {
+        let mut problems = vec![];
+        for (name, _) in self.list()? {
+            let text = self.read(&name)?.unwrap_or_default();
+            if let Err(err) = validate(&name, &text) {
+                problems.push((name, err.to_string()));
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Removes the cached files for `day`, or for every day if `None`, and
+    /// returns how many were removed. Recorded answers are kept.
+    pub fn clear(&self, day: Option<u32>) -> Result<usize> {
+        let prefix = day.map(|day| format!("day{:02}.", day));
+        let mut removed = 0;
+        for (name, _) in self.list()? {
+            let matches = match &prefix {
+                Some(prefix) => name.starts_with(prefix),
+                None => !KEPT_FILES.contains(&name.as_str()),
+            };
+            if matches {
+                self.remove(&name)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Removes a cached file.
+    pub fn remove(&self, name: &str) -> Result<()> {
+        let path = self.path(name)?;
+        fs::remove_file(&path).with_context(|| format!("failed to remove {}", path.display()))
+    }
+
+    /// Formats the list of cached files.
+    pub fn report(&self) -> Result<String> {
+        let files = self.list()?;
+        let dir = self
+            .existing_dir()?
+            .unwrap_or_else(|| self.year_dir.clone());
+        let mut out = format!("{}\n", dir.display());
+        for (name, size) in &files {
+            writeln!(out, "  {:<24} {:>8} bytes", name, size).unwrap();
+        }
+        write!(out, "{} file(s)", files.len()).unwrap();
+        Ok(out)
+    }
+}
+
+/// Writes to a temporary file next to `path` and then renames it into place,
+/// so that an interrupted write never leaves a partial file behind.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("invalid path {}", path.display()))?;
+    let tmp = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        process::id()
+    ));
+    fs::write(&tmp, contents).with_context(|| format!("failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Checks that a response is plausibly what the named file should contain,
+/// rather than an error page or a login prompt.
+fn validate(name: &str, contents: &str) -> Result<()> {
+    let lower = contents.to_lowercase();
+    if name.ends_with(".json") {
+        serde_json::from_str::<serde_json::Value>(contents).context("not valid JSON")?;
+    } else if name.ends_with(".html") {
+        if !contents.contains("<article") {
+            return Err(anyhow!("no puzzle description found"));
+        }
+    } else if contents.trim().is_empty() {
+        return Err(anyhow!("empty"));
+    } else if !name.contains(".example") && lower.contains("please log in") {
+        return Err(anyhow!(
+            "looks like a login prompt, is the session cookie valid?"
+        ));
+    } else if !name.contains(".example")
+        && (lower.trim_start().starts_with("<!doctype") || lower.contains("<html"))
+    {
+        return Err(anyhow!("looks like an HTML page"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::Cache;
+    use crate::config::Config;
+
+    #[test]
+    fn test_cache() {
+        let root = env::temp_dir().join(format!("aoc-cache-{}", process::id()));
+        let cache = Cache::new(&root, 2021, "abc123");
+        let other = Cache::new(&root, 2021, "def456");
+        assert_ne!(cache.dir().unwrap(), other.dir().unwrap());
+        assert!(cache.dir().unwrap().starts_with(root.join("2021")));
+
+        assert_eq!(cache.read("day01.txt").unwrap(), None);
+        cache.write("day01.txt", "1\n2\n").unwrap();
+        cache.write("day02.txt", "forward 1\n").unwrap();
+        cache.write("answers.json", "{}").unwrap();
+        assert_eq!(cache.read("day01.txt").unwrap().as_deref(), Some("1\n2\n"));
+        assert_eq!(other.read("day01.txt").unwrap(), None);
+
+        for (name, contents) in [
+            ("day03.txt", ""),
+            (
+                "day03.txt",
+                "<!DOCTYPE html>\n<html><body>500</body></html>",
+            ),
+            (
+                "day03.txt",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            ("day03.puzzle.html", "<html><body>Not found</body></html>"),
+            ("day03.examples.json", "{"),
+        ] {
+            assert!(cache.write(name, contents).is_err(), "{}", contents);
+        }
+        assert_eq!(cache.list().unwrap().len(), 3);
+
+        fs::write(cache.path("day04.txt").unwrap(), "<html>").unwrap();
+        let problems = cache.verify().unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].0, "day04.txt");
+
+        assert_eq!(cache.clear(Some(1)).unwrap(), 1);
+        assert_eq!(cache.clear(None).unwrap(), 2);
+        let names: Vec<_> = cache.list().unwrap().into_iter().map(|f| f.0).collect();
+        assert_eq!(names, vec!["answers.json"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_cache_without_session() {
+        let root = env::temp_dir().join(format!("aoc-cache-offline-{}", process::id()));
+        let offline = Cache::for_account(&root, 2021, None);
+        assert_eq!(offline.read("day01.txt").unwrap(), None);
+        assert!(offline.list().unwrap().is_empty());
+        assert!(offline.write("day01.txt", "1\n").is_err());
+
+        let cache = Cache::new(&root, 2021, "abc123");
+        cache.write("day01.txt", "1\n").unwrap();
+        let offline = Cache::for_account(&root, 2021, None);
+        assert_eq!(offline.dir().unwrap(), cache.dir().unwrap());
+        assert_eq!(offline.read("day01.txt").unwrap().as_deref(), Some("1\n"));
+
+        Cache::new(&root, 2021, "def456")
+            .write("day01.txt", "2\n")
+            .unwrap();
+        assert!(Cache::for_account(&root, 2021, None)
+            .read("day01.txt")
+            .is_err());
+        let account = cache.dir().unwrap().file_name().unwrap().to_owned();
+        let chosen = Cache::for_account(&root, 2021, account.into_string().ok());
+        assert_eq!(chosen.read("day01.txt").unwrap().as_deref(), Some("1\n"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_from_config() {
+        let root = env::temp_dir().join(format!("aoc-cache-config-{}", process::id()));
+        let mut config = Config::default();
+        config.cache_dir = Some(root.clone());
+        config.account = Some("offline".to_string());
+        let cache = Cache::from_config(&config, 2021).unwrap();
+        assert_eq!(cache.dir().unwrap(), root.join("2021").join("offline"));
+
+        config.session_file = Some(root.join("missing"));
+        let err = Cache::from_config(&config, 2021).err().unwrap();
+        assert!(err.to_string().starts_with("failed to read"), "{}", err);
+
+        config.session = Some("abc123".to_string());
+        let cache = Cache::from_config(&config, 2021).unwrap();
+        assert_eq!(
+            cache.dir().unwrap(),
+            Cache::new(&root, 2021, "abc123").dir().unwrap()
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use std::{ops::RangeInclusive, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{ArgAction, Args, Parser, Subcommand};
+
+use crate::{
+    input::{InputSource, Slice},
+    output::Format,
+    solution::Registry,
+};
+
+/// The year of the first event.
+const FIRST_YEAR: i32 = 2015;
+/// The last day of the event.
+const LAST_DAY: u32 = 25;
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions")]
+pub struct Cli {
+    /// The year of the event, by default `year` from aoc.toml or the latest
+    /// one with any solutions.
+    #[arg(long, global = true, value_parser = clap::value_parser!(i32).range(FIRST_YEAR as i64..))]
+    pub year: Option<i32>,
+    /// Log what's going on to stderr: -v for debug output, -vv for tracing
+    /// output and span timings. `$AOC_LOG` takes precedence, e.g.
+    /// `AOC_LOG=aoc_2021::y2021::day04=debug`.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Runs the solutions for the selected days and parts.
+    Run {
+        /// Days to run: a day ("3"), a range ("1-5"), a comma-separated list
+        /// of either, "all" or "latest".
+        days: DaySelector,
+        /// Part to run: "1", "2" or "both".
+        #[arg(default_value = "both")]
+        part: PartSelector,
+        /// How to format the results, by default `format` from aoc.toml or
+        /// plain.
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+        /// Seconds to allow for parsing or for each part, or 0 for no limit.
+        /// By default `timeouts.solve` from aoc.toml, or 60.
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Number of days to run concurrently.
+        #[arg(long, short, default_value_t = 1)]
+        jobs: usize,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Reruns a day against its examples and puzzle input whenever they
+    /// change, rebuilding first if its source changes.
+    Watch {
+        /// The day to watch, e.g. "3" or "latest".
+        day: DaySelector,
+        /// Part to run: "1", "2" or "both".
+        #[arg(default_value = "both")]
+        part: PartSelector,
+        /// Seconds to allow for parsing or for each part, or 0 for no limit.
+        /// By default `timeouts.solve` from aoc.toml, or 60.
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// Downloads and caches the input for the selected days.
+    Fetch {
+        days: DaySelector,
+        /// Also cache the puzzle descriptions, their examples and the
+        /// examples' answers.
+        #[arg(long, visible_alias = "examples")]
+        puzzle: bool,
+    },
+    /// Shows a day's puzzle description from the cache.
+    Show {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=LAST_DAY as i64))]
+        day: u32,
+        /// Print Markdown instead of formatted text.
+        #[arg(long)]
+        markdown: bool,
+        /// Column to wrap text at.
+        #[arg(long, default_value_t = 80)]
+        width: usize,
+    },
+    /// Submits an answer to the website.
+    Submit {
+        /// The day to submit for, e.g. "3" or "latest".
+        day: DaySelector,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// The answer to submit. If omitted, the solution is run to get it.
+        answer: Option<String>,
+    },
+    /// Records a known-correct answer, to be checked by `verify`.
+    Record {
+        /// The day to record for, e.g. "3" or "latest".
+        day: DaySelector,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// The answer to record. If omitted, the solution is run to get it.
+        answer: Option<String>,
+    },
+    /// Checks the solutions' answers against the recorded ones.
+    Verify {
+        #[arg(default_value = "all")]
+        days: DaySelector,
+        /// Also write a JUnit XML report to this path.
+        #[arg(long)]
+        junit: Option<PathBuf>,
+    },
+    /// Times parsing and solving the selected days over many iterations.
+    Bench {
+        #[arg(default_value = "all")]
+        days: DaySelector,
+        /// Untimed iterations to run first.
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+        /// Timed iterations to run.
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        /// Save the results as a baseline file.
+        #[arg(long)]
+        save: Option<PathBuf>,
+        /// Compare the results against a saved baseline file.
+        #[arg(long)]
+        compare: Option<PathBuf>,
+        /// Percentage by which a median must grow to count as a regression.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Shows a private leaderboard's scores, stars and completion times.
+    Leaderboard {
+        /// The leaderboard's ID, the number at the end of its URL.
+        id: u64,
+    },
+    /// Manages the cache of downloaded inputs, puzzles and examples.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Shows the settings from aoc.toml files and the environment.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Generates the module for a new day, with tests against the example
+    /// from the puzzle text.
+    New {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=LAST_DAY as i64))]
+        day: u32,
+        /// Don't fetch the puzzle, and leave its title and example as TODOs.
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Lists the days that have solutions.
+    List,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Lists the cached files.
+    List,
+    /// Checks that none of the cached files are error pages, login prompts or
+    /// otherwise corrupt.
+    Verify,
+    /// Removes the cached files for a day, or for every day. Recorded answers
+    /// are kept.
+    Clear {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=LAST_DAY as i64))]
+        day: Option<u32>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Prints the effective settings and where each came from, with the
+    /// session cookie redacted.
+    Show,
+}
+
+/// Options for choosing which input to run against.
+#[derive(Debug, Args)]
+pub struct InputArgs {
+    /// Read the input from this file instead of the puzzle input.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["stdin", "example"])]
+    input: Option<PathBuf>,
+    /// Read the input from stdin instead of the puzzle input.
+    #[arg(long, conflicts_with = "example")]
+    stdin: bool,
+    /// Run against the day's Nth cached example instead of the puzzle input.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    example: Option<u64>,
+    /// Only use the first N lines of the input.
+    #[arg(long, value_name = "N", conflicts_with = "lines")]
+    head: Option<usize>,
+    /// Only use this range of lines of the input, e.g. "3..7", "3.." or "..7",
+    /// numbered from 1.
+    #[arg(long, value_name = "A..B")]
+    lines: Option<Slice>,
+    /// Don't run against the day's cached examples before the puzzle input.
+    #[arg(long)]
+    no_examples: bool,
+}
+
+impl InputArgs {
+    /// Where the input should be read from.
+    pub fn source(&self) -> InputSource {
+        if let Some(path) = &self.input {
+            InputSource::File(path.clone())
+        } else if self.stdin {
+            InputSource::Stdin
+        } else if let Some(n) = self.example {
+            InputSource::Example(n as usize)
+        } else {
+            InputSource::Puzzle
+        }
+    }
+
+    /// Which lines of the input to use, if not all of them.
+    pub fn slice(&self) -> Option<Slice> {
+        self.head.map(Slice::Head).or(self.lines)
+    }
+
+    /// Whether to check the day's examples before running against the input.
+    pub fn with_examples(&self) -> bool {
+        self.source() == InputSource::Puzzle && !self.no_examples
+    }
+}
+
+/// A single part of a single day's problem.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Problem {
+    pub day: u32,
+    pub part: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DaySelector {
+    All,
+    Latest,
+    Ranges(Vec<RangeInclusive<u32>>),
+}
+
+impl DaySelector {
+    /// Resolves the selector to the list of days it refers to, in order,
+    /// failing if any of them has no solution.
+    pub fn resolve(&self, registry: &Registry) -> Result<Vec<u32>> {
+        let mut days: Vec<u32> = match self {
+            DaySelector::All => registry.iter().map(|s| s.day()).collect(),
+            DaySelector::Latest => registry.iter().map(|s| s.day()).max().into_iter().collect(),
+            DaySelector::Ranges(ranges) => ranges.iter().cloned().flatten().collect(),
+        };
+        days.sort_unstable();
+        days.dedup();
+
+        let missing: Vec<String> = days
+            .iter()
+            .filter(|&&day| registry.get(day).is_none())
+            .map(|day| day.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow!("no solution for day(s) {}", missing.join(", ")));
+        }
+        if days.is_empty() {
+            return Err(anyhow!("no days selected"));
+        }
+        Ok(days)
+    }
+
+    /// Like `resolve`, but fails unless the selector refers to exactly one day.
+    pub fn resolve_one(&self, registry: &Registry) -> Result<u32> {
+        match self.resolve(registry)?[..] {
+            [day] => Ok(day),
+            _ => Err(anyhow!("expected a single day")),
+        }
+    }
+}
+
+impl FromStr for DaySelector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "all" => return Ok(DaySelector::All),
+            "latest" => return Ok(DaySelector::Latest),
+            _ => {}
+        }
+        s.split(',')
+            .map(|part| {
+                let range = match part.split_once('-') {
+                    Some((start, end)) => parse_day(start)?..=parse_day(end)?,
+                    None => parse_day(part)?..=parse_day(part)?,
+                };
+                if range.is_empty() {
+                    return Err(anyhow!("empty day range '{}'", part));
+                }
+                Ok(range)
+            })
+            .collect::<Result<_>>()
+            .map(DaySelector::Ranges)
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32> {
+    let day: u32 = s
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid day '{}'", s))?;
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(anyhow!(
+            "day must be between 1 and {}, got {}",
+            LAST_DAY,
+            day
+        ));
+    }
+    Ok(day)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartSelector {
+    One,
+    Two,
+    Both,
+}
+
+impl PartSelector {
+    /// The part numbers selected, in order.
+    pub fn parts(self) -> &'static [u32] {
+        match self {
+            PartSelector::One => &[1],
+            PartSelector::Two => &[2],
+            PartSelector::Both => &[1, 2],
+        }
+    }
+}
+
+impl FromStr for PartSelector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(PartSelector::One),
+            "2" => Ok(PartSelector::Two),
+            "both" => Ok(PartSelector::Both),
+            _ => Err(anyhow!("part must be 1, 2 or both, got '{}'", s)),
+        }
+    }
+}
+
+/// Expands the selected days and parts into the individual problems to run.
+pub fn problems(days: &[u32], part: PartSelector) -> Vec<Problem> {
+    days.iter()
+        .flat_map(|&day| part.parts().iter().map(move |&part| Problem { day, part }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::{problems, Cli, Command, DaySelector, PartSelector, Problem};
+    use crate::{
+        input::{InputSource, Slice},
+        solution::Registry,
+    };
+
+    #[test]
+    fn test_parse_day_selector() {
+        assert_eq!("all".parse::<DaySelector>().unwrap(), DaySelector::All);
+        assert_eq!(
+            "latest".parse::<DaySelector>().unwrap(),
+            DaySelector::Latest
+        );
+        assert_eq!(
+            "3".parse::<DaySelector>().unwrap(),
+            DaySelector::Ranges(vec![3..=3])
+        );
+        assert_eq!(
+            "1-5,7".parse::<DaySelector>().unwrap(),
+            DaySelector::Ranges(vec![1..=5, 7..=7])
+        );
+        assert!("0".parse::<DaySelector>().is_err());
+        assert!("26".parse::<DaySelector>().is_err());
+        assert!("5-1".parse::<DaySelector>().is_err());
+        assert!("three".parse::<DaySelector>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_day_selector() {
+        let registry = Registry::new(2021);
+        let latest = registry.iter().map(|s| s.day()).max().unwrap();
+        assert_eq!(
+            "3,1-2,2"
+                .parse::<DaySelector>()
+                .unwrap()
+                .resolve(&registry)
+                .unwrap(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            DaySelector::Latest.resolve(&registry).unwrap(),
+            vec![latest]
+        );
+        assert_eq!(
+            DaySelector::All.resolve(&registry).unwrap().len(),
+            registry.iter().count()
+        );
+        assert!("25"
+            .parse::<DaySelector>()
+            .unwrap()
+            .resolve(&registry)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_part_selector() {
+        assert_eq!("1".parse::<PartSelector>().unwrap(), PartSelector::One);
+        assert_eq!("both".parse::<PartSelector>().unwrap(), PartSelector::Both);
+        assert!("3".parse::<PartSelector>().is_err());
+    }
+
+    #[test]
+    fn test_problems() {
+        assert_eq!(
+            problems(&[4, 5], PartSelector::Both),
+            vec![
+                Problem { day: 4, part: 1 },
+                Problem { day: 4, part: 2 },
+                Problem { day: 5, part: 1 },
+                Problem { day: 5, part: 2 },
+            ]
+        );
+        assert_eq!(
+            problems(&[4], PartSelector::Two),
+            vec![Problem { day: 4, part: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_input_args() {
+        let input = |args: &[&str]| match Cli::try_parse_from(args).ok()?.command {
+            Command::Run { input, .. } => Some((input.source(), input.slice())),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            input(&["aoc", "run", "1"]).unwrap(),
+            (InputSource::Puzzle, None)
+        );
+        assert_eq!(
+            input(&["aoc", "run", "1", "--example", "2", "--head", "3"]).unwrap(),
+            (InputSource::Example(2), Some(Slice::Head(3)))
+        );
+        assert_eq!(
+            input(&["aoc", "run", "1", "--stdin", "--lines", "2.."]).unwrap(),
+            (
+                InputSource::Stdin,
+                Some(Slice::Lines {
+                    start: Some(2),
+                    end: None
+                })
+            )
+        );
+        assert!(input(&["aoc", "run", "1", "--stdin", "--example", "1"]).is_none());
+        assert!(input(&["aoc", "run", "1", "--head", "1", "--lines", "1..2"]).is_none());
+        assert!(input(&["aoc", "run", "1", "--example", "0"]).is_none());
+    }
+}
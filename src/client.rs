@@ -0,0 +1,601 @@
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    sync::OnceLock,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::{blocking::RequestBuilder, StatusCode};
+use tracing::{debug, warn};
+
+use crate::{
+    cache::write_atomic,
+    config::{self, Config},
+};
+
+pub(crate) static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies us to the site's maintainers, as they ask automated tools to.
+const USER_AGENT: &str = "github.com/orn688/advent-of-code-2021 by orn688";
+pub(crate) const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const TIMEOUT: Duration = Duration::from_secs(30);
+/// How many times to retry a download that failed for a transient reason.
+const RETRIES: u32 = 3;
+/// How long to wait before the first retry, doubling for each one after it.
+const BACKOFF: Duration = Duration::from_secs(1);
+/// How long to wait after being rate limited, if the website doesn't say.
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+/// The longest the website can ask us to wait before a retry, beyond which
+/// it's better to give up and let the user try again later.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(5 * 60);
+/// The minimum time between requests, across all runs.
+const THROTTLE_INTERVAL: Duration = Duration::from_secs(2);
+/// Where the time of the last request is kept, relative to the cache root.
+const THROTTLE_FILE: &str = "last-request";
+
+lazy_static! {
+    static ref MAIN_RE: Regex = Regex::new(r"(?s)<main>(.*)</main>").unwrap();
+    static ref TAG_RE: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref WAIT_RE: Regex = Regex::new(r"(\d+)([hms])").unwrap();
+}
+
+/// The website rejected the session cookie, most likely because it expired.
+#[derive(Debug)]
+pub struct SessionExpired;
+
+impl fmt::Display for SessionExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the session cookie was rejected, it has probably expired: log in to \
+             adventofcode.com again and copy the new session cookie into ${} or {}",
+            config::SESSION_ENV_VAR_NAME,
+            config::FILE
+        )
+    }
+}
+
+impl std::error::Error for SessionExpired {}
+
+/// Why a request failed, and whether it's worth trying again.
+enum Failure {
+    /// A timeout, a failure to connect or a server error, which might go away
+    /// if tried again shortly.
+    Transient(anyhow::Error),
+    /// Too many requests, along with how long the website asked us to wait,
+    /// if it said.
+    RateLimited(anyhow::Error, Option<Duration>),
+    Permanent(anyhow::Error),
+}
+
+impl Failure {
+    /// Classifies an error from sending a request or reading its response.
+    /// Only timeouts and failures to connect are worth retrying; anything
+    /// else, like an invalid URL, will fail the same way every time.
+    fn from_reqwest(err: reqwest::Error, context: &'static str) -> Failure {
+        let transient = err.is_timeout() || err.is_connect();
+        let err = anyhow::Error::from(err).context(context);
+        if transient {
+            Failure::Transient(err)
+        } else {
+            Failure::Permanent(err)
+        }
+    }
+}
+
+/// Talks to the Advent of Code website on behalf of a logged-in user.
+pub struct Client {
+    base_url: String,
+    session: String,
+    http: reqwest::blocking::Client,
+    backoff: Duration,
+    rate_limit_backoff: Duration,
+    throttle: Option<Throttle>,
+}
+
+impl Client {
+    /// Returns a client shared by the whole process, created from the
+    /// environment the first time it's needed.
+    pub fn shared() -> Result<&'static Client> {
+        static SHARED: OnceLock<Client> = OnceLock::new();
+        if let Some(client) = SHARED.get() {
+            return Ok(client);
+        }
+        let client = Client::from_env()?;
+        Ok(SHARED.get_or_init(|| client))
+    }
+
+    /// Creates a client using the session cookie, base URL and timeouts from
+    /// the config. Requests are throttled using a file in the cache
+    /// directory, so that separate runs don't hammer the site either.
+    pub fn from_env() -> Result<Client> {
+        let config = Config::shared()?;
+        let mut client = Client::with_timeouts(
+            config.base_url(),
+            &config.session()?,
+            config.connect_timeout(),
+            config.request_timeout(),
+        )?;
+        client.throttle = Some(Throttle {
+            path: config.cache_dir()?.join(THROTTLE_FILE),
+            interval: THROTTLE_INTERVAL,
+        });
+        Ok(client)
+    }
+
+    /// Creates a client with the default timeouts.
+    pub fn new(base_url: &str, session: &str) -> Result<Client> {
+        Client::with_timeouts(base_url, session, CONNECT_TIMEOUT, TIMEOUT)
+    }
+
+    /// Creates a client that gives up on connecting after `connect_timeout`,
+    /// and on each request after `timeout`.
+    pub fn with_timeouts(
+        base_url: &str,
+        session: &str,
+        connect_timeout: Duration,
+        timeout: Duration,
+    ) -> Result<Client> {
+        let http = reqwest::blocking::ClientBuilder::new()
+            .user_agent(USER_AGENT)
+            .connect_timeout(connect_timeout)
+            .timeout(timeout)
+            .build()?;
+        Ok(Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            http,
+            backoff: BACKOFF,
+            rate_limit_backoff: RATE_LIMIT_BACKOFF,
+            throttle: None,
+        })
+    }
+
+    /// Downloads the day's puzzle input.
+    pub fn download_input(&self, year: i32, day: u32) -> Result<String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Downloads the HTML of the day's puzzle page, which includes part 2
+    /// once part 1 has been solved.
+    pub fn download_puzzle(&self, year: i32, day: u32) -> Result<String> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+
+    /// Downloads a private leaderboard as JSON. The website asks that this is
+    /// done at most every 15 minutes, see `leaderboard::load`.
+    pub fn download_leaderboard(&self, year: i32, id: u64) -> Result<String> {
+        self.get(&format!("/{}/leaderboard/private/view/{}.json", year, id))
+    }
+
+    /// Sends a GET request, retrying with exponential backoff if it fails for
+    /// a reason that might go away, and after the time the website asks for
+    /// if it's rate limited.
+    fn get(&self, path: &str) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!(url, "GET");
+        let mut delay = self.backoff;
+        let mut retries = 0;
+        loop {
+            let (err, wait) = match self.send(self.http.get(&url)) {
+                Ok(body) => return Ok(body),
+                Err(Failure::Permanent(err)) => return Err(err),
+                Err(Failure::Transient(err)) => (err, delay),
+                Err(Failure::RateLimited(err, retry_after)) => {
+                    (err, retry_after.unwrap_or(self.rate_limit_backoff))
+                }
+            };
+            if retries == RETRIES {
+                return Err(err.context(format!("giving up after {} retries", RETRIES)));
+            }
+            if wait > MAX_RETRY_AFTER {
+                return Err(err.context(format!(
+                    "rate limited, try again in {} minute(s)",
+                    wait.as_secs().div_ceil(60)
+                )));
+            }
+            warn!(url, ?wait, "request failed, retrying: {:#}", err);
+            thread::sleep(wait);
+            delay *= 2;
+            retries += 1;
+        }
+    }
+
+    /// Submits an answer for the given day and part, and classifies the
+    /// website's response. Submissions are never retried, since the first
+    /// one may have been counted.
+    pub fn submit(&self, year: i32, day: u32, part: u32, answer: &str) -> Result<SubmitOutcome> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let request = self
+            .http
+            .post(url)
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())]);
+        let html = self.send(request).map_err(|f| match f {
+            Failure::Permanent(err) | Failure::Transient(err) | Failure::RateLimited(err, _) => err,
+        })?;
+        SubmitOutcome::from_html(&html)
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<String, Failure> {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait().map_err(Failure::Permanent)?;
+        }
+        let resp = request
+            .header(reqwest::header::COOKIE, self.cookie())
+            .send()
+            .map_err(|err| Failure::from_reqwest(err, "request failed"))?;
+        let (status, url) = (resp.status(), resp.url().clone());
+        // Only the number of seconds form is handled; a date falls back to the
+        // default backoff.
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        let body = resp
+            .text()
+            .map_err(|err| Failure::from_reqwest(err, "failed to read response"))?;
+        match status {
+            _ if status.is_success() => Ok(body),
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(Failure::Permanent(SessionExpired.into()))
+            }
+            StatusCode::NOT_FOUND => Err(Failure::Permanent(anyhow!(
+                "{} not found, is the puzzle unlocked yet?",
+                url
+            ))),
+            StatusCode::TOO_MANY_REQUESTS => Err(Failure::RateLimited(
+                anyhow!("{} returned {}", url, status),
+                retry_after,
+            )),
+            _ if status.is_server_error() => {
+                Err(Failure::Transient(anyhow!("{} returned {}", url, status)))
+            }
+            _ => Err(Failure::Permanent(anyhow!("{} returned {}", url, status))),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Keeps requests at least `interval` apart, remembering when the last one
+/// was made in a file so that the limit holds across runs.
+struct Throttle {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    fn wait(&self) -> Result<()> {
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| text.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last) = last {
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Round up, so that the next wait is never cut short by truncation.
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() + 1;
+        write_atomic(&self.path, &now.to_string()).context("failed to record request time")
+    }
+}
+
+/// The website's verdict on a submitted answer.
+#[derive(Debug, PartialEq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Incorrect, with no hint as to which direction.
+    Wrong,
+    /// An answer was submitted too recently; the given time must pass before
+    /// another one is accepted.
+    RateLimited(Duration),
+    /// The part has already been solved, or part 1 hasn't been solved yet.
+    AlreadySolved,
+}
+
+impl SubmitOutcome {
+    fn from_html(html: &str) -> Result<SubmitOutcome> {
+        let text = response_text(html);
+        let outcome = if text.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if text.contains("You gave an answer too recently") {
+            SubmitOutcome::RateLimited(parse_wait(&text))
+        } else if text.contains("Did you already complete it") {
+            SubmitOutcome::AlreadySolved
+        } else if text.contains("your answer is too high") {
+            SubmitOutcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            SubmitOutcome::TooLow
+        } else if text.contains("That's not the right answer") {
+            SubmitOutcome::Wrong
+        } else {
+            return Err(anyhow!("unrecognized response: {}", text));
+        };
+        Ok(outcome)
+    }
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct!"),
+            SubmitOutcome::TooHigh => write!(f, "wrong, too high"),
+            SubmitOutcome::TooLow => write!(f, "wrong, too low"),
+            SubmitOutcome::Wrong => write!(f, "wrong"),
+            SubmitOutcome::RateLimited(wait) => {
+                write!(f, "submitted too recently, wait {:?}", wait)
+            }
+            SubmitOutcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Extracts the plain text of the `<main>` element of a response, which is
+/// where the message about the submission lives.
+fn response_text(html: &str) -> String {
+    let main = MAIN_RE
+        .captures(html)
+        .and_then(|caps| caps.get(1))
+        .map_or(html, |m| m.as_str());
+    TAG_RE
+        .replace_all(main, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses a wait time like "You have 1m 23s left to wait" into a duration.
+fn parse_wait(text: &str) -> Duration {
+    let remainder = text
+        .split_once("You have ")
+        .map_or("", |(_, rest)| rest.split(" left").next().unwrap_or(""));
+    let secs = WAIT_RE
+        .captures_iter(remainder)
+        .map(|caps| {
+            let n: u64 = caps[1].parse().unwrap_or(0);
+            match &caps[2] {
+                "h" => n * 3600,
+                "m" => n * 60,
+                _ => n,
+            }
+        })
+        .sum();
+    Duration::from_secs(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        io::{Read, Write},
+        net::TcpListener,
+        process,
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    use super::{Client, SessionExpired, SubmitOutcome, Throttle, RETRIES};
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><header>too low too high</header><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    /// Serves one HTTP request per response, with the given status and body,
+    /// returning the base URL to send them to and a handle that yields the raw
+    /// requests.
+    fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        serve_with_headers(
+            responses
+                .into_iter()
+                .map(|(status, body)| (status, "", body))
+                .collect(),
+        )
+    }
+
+    /// Like `serve`, with extra header lines in each response.
+    fn serve_with_headers(
+        responses: Vec<(u16, &'static str, String)>,
+    ) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, headers, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, rest)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(|v| v.parse().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if rest.len() >= length {
+                            break;
+                        }
+                    }
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                requests.push(String::from_utf8(request).unwrap());
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    fn client(base_url: &str) -> Client {
+        let mut client = Client::new(base_url, "abc123").unwrap();
+        client.backoff = Duration::from_millis(1);
+        client.rate_limit_backoff = Duration::from_millis(1);
+        client
+    }
+
+    #[test]
+    fn test_classify_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                SubmitOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high. Please wait one minute.",
+                SubmitOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmitOutcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                SubmitOutcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.",
+                SubmitOutcome::RateLimited(Duration::from_secs(65)),
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                SubmitOutcome::AlreadySolved,
+            ),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(SubmitOutcome::from_html(&page(message)).unwrap(), expected);
+        }
+        assert!(SubmitOutcome::from_html(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, handle) = serve(vec![(200, page("<span>That's the right answer!</span>"))]);
+        let outcome = client(&base_url).submit(2021, 3, 2, "230").unwrap();
+        assert_eq!(outcome, SubmitOutcome::Correct);
+
+        let request = &handle.join().unwrap()[0];
+        assert!(request.starts_with("POST /2021/day/3/answer "));
+        assert!(request.contains("cookie: session=abc123"));
+        assert!(request.ends_with("level=2&answer=230"));
+    }
+
+    #[test]
+    fn test_download_leaderboard() {
+        let json = include_str!("../tests/fixtures/leaderboard.json");
+        let (base_url, handle) = serve(vec![(200, json.to_string())]);
+        let body = client(&base_url).download_leaderboard(2021, 101).unwrap();
+        assert_eq!(body, json);
+        let request = &handle.join().unwrap()[0];
+        assert!(request.starts_with("GET /2021/leaderboard/private/view/101.json "));
+    }
+
+    #[test]
+    fn test_retries_transient_errors() {
+        let responses = vec![
+            (500, String::new()),
+            (429, String::new()),
+            (200, "1\n2\n".to_string()),
+        ];
+        let (base_url, handle) = serve(responses);
+        assert_eq!(client(&base_url).download_input(2021, 1).unwrap(), "1\n2\n");
+        let requests = handle.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].starts_with("GET /2021/day/1/input "));
+        assert!(requests[2].contains("user-agent: github.com/orn688/advent-of-code-2021"));
+
+        let (base_url, handle) = serve(vec![(503, String::new()); RETRIES as usize + 1]);
+        let err = client(&base_url).download_input(2021, 1).unwrap_err();
+        assert!(format!("{:#}", err).starts_with("giving up after 3 retries"));
+        assert_eq!(handle.join().unwrap().len(), RETRIES as usize + 1);
+    }
+
+    #[test]
+    fn test_does_not_retry_permanent_errors() {
+        let responses = vec![(500, String::new()), (404, String::new())];
+        let (base_url, handle) = serve(responses);
+        let err = client(&base_url).download_input(2021, 1).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("not found"), "{}", message);
+        assert!(!message.contains("giving up"), "{}", message);
+        assert_eq!(handle.join().unwrap().len(), 2);
+
+        let err = client("not a url").download_input(2021, 1).unwrap_err();
+        assert!(!format!("{:#}", err).contains("giving up"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_honours_retry_after() {
+        let responses = vec![
+            (429, "Retry-After: 0\r\n", String::new()),
+            (200, "", "1\n".to_string()),
+        ];
+        let (base_url, handle) = serve_with_headers(responses);
+        assert_eq!(client(&base_url).download_input(2021, 1).unwrap(), "1\n");
+        assert_eq!(handle.join().unwrap().len(), 2);
+
+        let responses = vec![(429, "Retry-After: 3600\r\n", String::new())];
+        let (base_url, handle) = serve_with_headers(responses);
+        let err = client(&base_url).download_input(2021, 1).unwrap_err();
+        assert!(
+            format!("{:#}", err).starts_with("rate limited, try again in 60 minute(s)"),
+            "{:#}",
+            err
+        );
+        assert_eq!(handle.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_session_expired() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let (base_url, handle) = serve(vec![(400, body.to_string())]);
+        let err = client(&base_url).download_input(2021, 1).unwrap_err();
+        assert!(err.downcast_ref::<SessionExpired>().is_some(), "{:#}", err);
+        assert_eq!(handle.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_throttle() {
+        let path = env::temp_dir().join(format!("aoc-throttle-{}", process::id()));
+        let throttle = Throttle {
+            path: path.clone(),
+            interval: Duration::from_millis(100),
+        };
+        let start = Instant::now();
+        throttle.wait().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(100));
+        throttle.wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        fs::remove_file(path).unwrap();
+    }
+}
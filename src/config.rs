@@ -0,0 +1,430 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{client, output::Format, solution};
+
+/// The name of config files, both project-local and user-level.
+pub const FILE: &str = "aoc.toml";
+
+pub(crate) static SESSION_ENV_VAR_NAME: &str = "AOC_SESSION_ID";
+pub(crate) static CACHE_DIR_ENV_VAR_NAME: &str = "AOC_CACHE_DIR";
+pub(crate) static BASE_URL_ENV_VAR_NAME: &str = "AOC_BASE_URL";
+
+/// How long to allow for parsing or for each part when no timeout is given.
+const DEFAULT_SOLVE_TIMEOUT: u64 = 60;
+
+/// Settings read from `aoc.toml` files and the environment.
+///
+/// Settings are layered, each layer overriding the ones before it: the user's
+/// `$XDG_CONFIG_HOME/aoc/aoc.toml` (or `~/.config/aoc/aoc.toml`), then the
+/// nearest `aoc.toml` in the current directory or its ancestors, then
+/// environment variables. Relative paths in a file are relative to the
+/// directory it's in. Every setting is optional, and the accessors fill in the
+/// defaults.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The session cookie, also settable with `$AOC_SESSION_ID`.
+    pub session: Option<String>,
+    /// A file holding the session cookie, so that it can be kept out of a
+    /// shared config file.
+    pub session_file: Option<PathBuf>,
+    /// The cache root, also settable with `$AOC_CACHE_DIR`.
+    pub cache_dir: Option<PathBuf>,
+    /// Which account's directory in the cache to use when there's no session
+    /// cookie, for working offline with more than one account cached.
+    pub account: Option<String>,
+    /// The year to use when `--year` isn't given.
+    pub year: Option<i32>,
+    /// The format `run` uses when `--format` isn't given.
+    pub format: Option<Format>,
+    /// The website to talk to, also settable with `$AOC_BASE_URL`.
+    pub base_url: Option<String>,
+    pub timeouts: Timeouts,
+    /// Where each setting came from, by name.
+    #[serde(skip)]
+    origins: BTreeMap<&'static str, String>,
+}
+
+/// Timeouts, in seconds.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timeouts {
+    /// How long to allow for parsing or for each part when `--timeout` isn't
+    /// given, or 0 for no limit.
+    pub solve: Option<u64>,
+    /// How long to wait to connect to the website.
+    pub connect: Option<u64>,
+    /// How long to wait for each response from the website.
+    pub request: Option<u64>,
+}
+
+impl Config {
+    /// Returns the config shared by the whole process, loaded the first time
+    /// it's needed.
+    pub fn shared() -> Result<&'static Config> {
+        static SHARED: OnceLock<Config> = OnceLock::new();
+        if let Some(config) = SHARED.get() {
+            return Ok(config);
+        }
+        let config = Config::load()?;
+        Ok(SHARED.get_or_init(|| config))
+    }
+
+    /// Loads the user's and the current project's config files, and applies
+    /// any environment variables on top.
+    pub fn load() -> Result<Config> {
+        let cwd = env::current_dir().context("can't find the current directory")?;
+        let user_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("aoc"));
+        let files: Vec<PathBuf> = user_dir
+            .map(|dir| dir.join(FILE))
+            .into_iter()
+            .chain(find_project_file(&cwd))
+            .filter(|path| path.is_file())
+            .collect();
+        Config::layered(&files, |name| env::var(name).ok())
+    }
+
+    /// Reads `files` in order of increasing precedence, followed by the
+    /// environment variables that `var` looks up.
+    fn layered(files: &[PathBuf], var: impl Fn(&str) -> Option<String>) -> Result<Config> {
+        let mut config = Config::default();
+        for (i, path) in files.iter().enumerate() {
+            // The user's config file is also the project's when run from its
+            // directory.
+            if files[..i].contains(path) {
+                continue;
+            }
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let file = Config::parse(&text, dir)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            config.overlay(file, |_| path.display().to_string());
+        }
+
+        let from_env = Config {
+            session: var(SESSION_ENV_VAR_NAME),
+            cache_dir: var(CACHE_DIR_ENV_VAR_NAME).map(PathBuf::from),
+            base_url: var(BASE_URL_ENV_VAR_NAME),
+            ..Config::default()
+        };
+        config.overlay(from_env, |key| {
+            let name = match key {
+                "session" => SESSION_ENV_VAR_NAME,
+                "cache_dir" => CACHE_DIR_ENV_VAR_NAME,
+                _ => BASE_URL_ENV_VAR_NAME,
+            };
+            format!("${}", name)
+        });
+        Ok(config)
+    }
+
+    /// Parses a config file, resolving relative paths against `dir`.
+    pub fn parse(text: &str, dir: &Path) -> Result<Config> {
+        let mut config: Config = toml::from_str(text)?;
+        for path in [&mut config.session_file, &mut config.cache_dir]
+            .into_iter()
+            .flatten()
+        {
+            *path = resolve(path, dir);
+        }
+        Ok(config)
+    }
+
+    /// Replaces the settings that `layer` has, recording `origin(key)` as
+    /// where each one came from. A layer that sets either `session` or
+    /// `session_file` replaces both, so that a project's token file isn't
+    /// shadowed by a token in the user's config.
+    fn overlay(&mut self, layer: Config, origin: impl Fn(&'static str) -> String) {
+        if layer.session.is_some() || layer.session_file.is_some() {
+            self.session = None;
+            self.session_file = None;
+            self.origins.remove("session");
+            self.origins.remove("session_file");
+        }
+        macro_rules! set {
+            ($($field:ident).+, $key:literal) => {
+                if let Some(value) = layer.$($field).+ {
+                    self.$($field).+ = Some(value);
+                    self.origins.insert($key, origin($key));
+                }
+            };
+        }
+        set!(session, "session");
+        set!(session_file, "session_file");
+        set!(cache_dir, "cache_dir");
+        set!(account, "account");
+        set!(year, "year");
+        set!(format, "format");
+        set!(base_url, "base_url");
+        set!(timeouts.solve, "timeouts.solve");
+        set!(timeouts.connect, "timeouts.connect");
+        set!(timeouts.request, "timeouts.request");
+    }
+
+    /// The session cookie, either given directly or read from the session
+    /// file.
+    pub fn session(&self) -> Result<String> {
+        if let Some(session) = &self.session {
+            return Ok(session.clone());
+        }
+        let path = self.session_file.as_ref().ok_or_else(|| {
+            anyhow!(
+                "no session cookie, set ${} or add `session` or `session_file` to {}",
+                SESSION_ENV_VAR_NAME,
+                FILE
+            )
+        })?;
+        let session = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        match session.trim() {
+            "" => Err(anyhow!("{} is empty", path.display())),
+            session => Ok(session.to_string()),
+        }
+    }
+
+    /// The directory the cache lives in, before namespacing by year and
+    /// account: `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc` unless set.
+    pub fn cache_dir(&self) -> Result<PathBuf> {
+        if let Some(dir) = &self.cache_dir {
+            return Ok(dir.clone());
+        }
+        if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+            return Ok(PathBuf::from(dir).join("aoc"));
+        }
+        env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".cache").join("aoc"))
+            .ok_or_else(|| {
+                anyhow!(
+                    "can't find a cache directory, set ${} or `cache_dir` in {}",
+                    CACHE_DIR_ENV_VAR_NAME,
+                    FILE
+                )
+            })
+    }
+
+    /// The year to use when none is given, by default the latest one with any
+    /// solutions.
+    pub fn year(&self) -> Result<i32> {
+        match self.year {
+            Some(year) => Ok(year),
+            None => solution::years()
+                .last()
+                .copied()
+                .ok_or_else(|| anyhow!("no solutions found, pass --year")),
+        }
+    }
+
+    /// The output format for `run`, plain text by default.
+    pub fn format(&self) -> Format {
+        self.format.unwrap_or(Format::Plain)
+    }
+
+    /// The website to talk to, adventofcode.com by default.
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(client::DEFAULT_BASE_URL)
+    }
+
+    /// How long to allow for parsing or for each part, if there's a limit.
+    pub fn solve_timeout(&self) -> Option<Duration> {
+        let secs = self.timeouts.solve.unwrap_or(DEFAULT_SOLVE_TIMEOUT);
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    /// How long to wait to connect to the website.
+    pub fn connect_timeout(&self) -> Duration {
+        self.timeouts
+            .connect
+            .map_or(client::CONNECT_TIMEOUT, Duration::from_secs)
+    }
+
+    /// How long to wait for each response from the website.
+    pub fn request_timeout(&self) -> Duration {
+        self.timeouts
+            .request
+            .map_or(client::TIMEOUT, Duration::from_secs)
+    }
+
+    /// Formats the effective settings as TOML, noting where each one came
+    /// from. The session cookie is never shown.
+    pub fn show(&self) -> String {
+        let quote = |s: &str| toml::Value::String(s.to_string()).to_string();
+        let session = match (&self.session, &self.session_file) {
+            (Some(_), _) => Some(("session", quote("<redacted>"))),
+            (None, Some(path)) => Some(("session_file", quote(&path.to_string_lossy()))),
+            (None, None) => None,
+        };
+        let cache_dir = self.cache_dir().map_or_else(
+            |err| format!("# {:#}", err),
+            |dir| quote(&dir.to_string_lossy()),
+        );
+        let year = self
+            .year()
+            .map_or_else(|err| format!("# {:#}", err), |year| year.to_string());
+        let format = self.format().to_possible_value().unwrap();
+        let secs = |timeout: Duration| timeout.as_secs().to_string();
+        let settings = [
+            ("cache_dir", cache_dir),
+            ("year", year),
+            ("format", quote(format.get_name())),
+            ("base_url", quote(self.base_url())),
+            (
+                "timeouts.solve",
+                secs(self.solve_timeout().unwrap_or_default()),
+            ),
+            ("timeouts.connect", secs(self.connect_timeout())),
+            ("timeouts.request", secs(self.request_timeout())),
+        ];
+
+        let mut out = String::new();
+        match session {
+            Some((key, value)) => self.show_setting(&mut out, key, &value),
+            None => writeln!(
+                out,
+                "# no session cookie, set ${} or `session` or `session_file`",
+                SESSION_ENV_VAR_NAME
+            )
+            .unwrap(),
+        }
+        if let Some(account) = &self.account {
+            self.show_setting(&mut out, "account", &quote(account));
+        }
+        for (key, value) in settings {
+            self.show_setting(&mut out, key, &value);
+        }
+        out.trim_end().to_string()
+    }
+
+    fn show_setting(&self, out: &mut String, key: &str, value: &str) {
+        let origin = self.origins.get(key).map_or("default", String::as_str);
+        writeln!(out, "{} = {}  # {}", key, value, origin).unwrap();
+    }
+}
+
+/// Finds the nearest config file in `dir` or its ancestors.
+fn find_project_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(FILE))
+        .find(|path| path.is_file())
+}
+
+/// Makes `path` absolute relative to `dir`, expanding a leading `~` to the
+/// home directory.
+fn resolve(path: &Path, dir: &Path) -> PathBuf {
+    if let (Ok(rest), Some(home)) = (path.strip_prefix("~"), env::var_os("HOME")) {
+        return PathBuf::from(home).join(rest);
+    }
+    dir.join(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path, process, time::Duration};
+
+    use super::Config;
+    use crate::output::Format;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+session_file = "secrets/session"
+cache_dir = "/var/cache/aoc"
+account = "0123456789ab"
+year = 2020
+format = "json"
+
+[timeouts]
+solve = 0
+request = 5
+"#,
+            Path::new("/home/me/aoc"),
+        )
+        .unwrap();
+        assert_eq!(
+            config.session_file.as_deref(),
+            Some(Path::new("/home/me/aoc/secrets/session"))
+        );
+        assert_eq!(
+            config.cache_dir.as_deref(),
+            Some(Path::new("/var/cache/aoc"))
+        );
+        assert_eq!(config.account.as_deref(), Some("0123456789ab"));
+        assert_eq!(config.year().unwrap(), 2020);
+        assert_eq!(config.format(), Format::Json);
+        assert_eq!(config.solve_timeout(), None);
+        assert_eq!(config.connect_timeout(), Duration::from_secs(10));
+        assert_eq!(config.request_timeout(), Duration::from_secs(5));
+
+        assert!(Config::parse("sesion = \"abc\"", Path::new("/")).is_err());
+        assert!(Config::parse("format = \"yaml\"", Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn test_layers() {
+        let dir = env::temp_dir().join(format!("aoc-config-{}", process::id()));
+        let project = dir.join("project");
+        fs::create_dir_all(&project).unwrap();
+        let user_file = dir.join("aoc.toml");
+        fs::write(
+            &user_file,
+            "session = \"user-token\"\nyear = 2020\nbase_url = \"http://user\"\n",
+        )
+        .unwrap();
+        let project_file = project.join("aoc.toml");
+        fs::write(
+            &project_file,
+            "session_file = \"session\"\nyear = 2021\n[timeouts]\nsolve = 5\n",
+        )
+        .unwrap();
+        fs::write(project.join("session"), "project-token\n").unwrap();
+        let files = [user_file.clone(), project_file.clone()];
+
+        let config = Config::layered(&files, |_| None).unwrap();
+        assert_eq!(config.session().unwrap(), "project-token");
+        assert_eq!(config.year().unwrap(), 2021);
+        assert_eq!(config.base_url(), "http://user");
+        assert_eq!(config.solve_timeout(), Some(Duration::from_secs(5)));
+
+        let config = Config::layered(&files, |name| match name {
+            "AOC_SESSION_ID" => Some("env-token".to_string()),
+            "AOC_CACHE_DIR" => Some("/tmp/aoc".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(config.session().unwrap(), "env-token");
+        let shown = config.show();
+        assert!(!shown.contains("token"), "{}", shown);
+        assert!(shown.contains("session = \"<redacted>\"  # $AOC_SESSION_ID\n"));
+        assert!(shown.contains("cache_dir = \"/tmp/aoc\"  # $AOC_CACHE_DIR\n"));
+        assert!(shown.contains(&format!("year = 2021  # {}\n", project_file.display())));
+        assert!(shown.contains(&format!(
+            "base_url = \"http://user\"  # {}\n",
+            user_file.display()
+        )));
+        assert!(shown.contains("format = \"plain\"  # default\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_session() {
+        let config = Config::default();
+        assert!(config.session().is_err());
+        assert!(config.show().starts_with("# no session cookie"));
+    }
+}
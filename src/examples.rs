@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{cache::Cache, input::example_name, puzzle};
+
+lazy_static! {
+    static ref PRE_RE: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    static ref ANSWER_RE: Regex = Regex::new(r"(?s)<code><em>(.*?)</em></code>").unwrap();
+    static ref TAG_RE: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+/// An answer given in the puzzle text, and the example it's the answer for.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExpectedAnswer {
+    /// Which example, numbered from 1, or `None` if it isn't known.
+    pub example: Option<usize>,
+    pub answer: String,
+}
+
+/// Expected example answers by part.
+pub type ExpectedAnswers = BTreeMap<u32, ExpectedAnswer>;
+
+/// The examples found on a puzzle page.
+#[derive(Debug, PartialEq)]
+pub struct Examples {
+    /// The contents of every code block that isn't blank, in order.
+    pub inputs: Vec<String>,
+    pub answers: ExpectedAnswers,
+}
+
+impl Examples {
+    /// Extracts the examples from a puzzle page.
+    ///
+    /// Every `<pre><code>` block is taken to be an example input, which isn't
+    /// always true (some show intermediate states), but it's up to the user
+    /// which ones to run. Blank blocks are skipped, since there's nothing to
+    /// run. The answer to each part is taken to be the last emphasized code in
+    /// that part's description. It's only tied to an example when the page has
+    /// just the one, since otherwise there's no telling which it's for; the
+    /// saved answers file can be edited by hand to fill the rest in.
+    pub fn from_html(html: &str) -> Examples {
+        let inputs: Vec<String> = PRE_RE
+            .captures_iter(html)
+            .map(|caps| text(&caps[1]))
+            .filter(|input| !input.trim().is_empty())
+            .collect();
+        let example = (inputs.len() == 1).then_some(1);
+        let answers = puzzle::articles(html)
+            .into_iter()
+            .zip(1..)
+            .filter_map(|(article, part)| {
+                let answer = ANSWER_RE.captures_iter(article).last()?;
+                Some((
+                    part,
+                    ExpectedAnswer {
+                        example,
+                        answer: text(&answer[1]),
+                    },
+                ))
+            })
+            .collect();
+        Examples { inputs, answers }
+    }
+
+    /// Caches the examples next to the day's input, as `dayNN.exampleN.txt`
+    /// plus `dayNN.examples.json` for the expected answers, and removes any
+    /// examples left over from a page that had more of them.
+    pub fn save(&self, cache: &Cache, day: u32) -> Result<()> {
+        for (n, input) in self.inputs.iter().enumerate() {
+            cache.write(&example_name(day, n + 1), input)?;
+        }
+        let prefix = format!("day{:02}.example", day);
+        for (name, _) in cache.list()? {
+            let n = name
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(".txt"))
+                .and_then(|n| n.parse::<usize>().ok());
+            if n.is_some_and(|n| n > self.inputs.len()) {
+                cache.remove(&name)?;
+            }
+        }
+        let text = serde_json::to_string_pretty(&self.answers)?;
+        cache.write(&answers_name(day), &(text + "\n"))
+    }
+}
+
+/// Loads the day's expected example answers, which are empty if the examples
+/// haven't been fetched.
+pub fn load_answers(cache: &Cache, day: u32) -> Result<ExpectedAnswers> {
+    let name = answers_name(day);
+    match cache.read(&name)? {
+        Some(text) => {
+            serde_json::from_str(&text).with_context(|| format!("failed to parse {}", name))
+        }
+        None => Ok(ExpectedAnswers::new()),
+    }
+}
+
+fn answers_name(day: u32) -> String {
+    format!("day{:02}.examples.json", day)
+}
+
+/// Strips the tags from a snippet of HTML and unescapes its entities.
+fn text(html: &str) -> String {
+    puzzle::unescape(&TAG_RE.replace_all(html, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{Examples, ExpectedAnswer};
+    use crate::cache::Cache;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 10: Syntax Scoring ---</h2>
+<p>For example:</p>
+<pre><code>[({(&lt;(())[]&gt;[[{[]{&lt;()&lt;&gt;&gt;
+<em>{</em>([(&lt;{}[&lt;&gt;[]}&gt;{[]{[(&lt;()&gt;
+</code></pre>
+<p>Stop at the first incorrect closing character on each corrupted line.</p>
+<pre><code>): 3 points.</code></pre>
+<pre><code>
+</code></pre>
+<p>The total is <code>2*3 + 57</code> = <code><em>26397</em></code> points.</p>
+</article>
+<p>Your puzzle answer was <code>392043</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The middle score is <code><em>288957</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_from_html() {
+        let examples = Examples::from_html(PAGE);
+        assert_eq!(
+            examples.inputs,
+            vec![
+                "[({(<(())[]>[[{[]{<()<>>\n{([(<{}[<>[]}>{[]{[(<()>\n",
+                "): 3 points.",
+            ]
+        );
+        // There's more than one example, so which the answers are for isn't
+        // known.
+        let answer = |answer: &str| ExpectedAnswer {
+            example: None,
+            answer: answer.to_string(),
+        };
+        assert_eq!(examples.answers.get(&1), Some(&answer("26397")));
+        assert_eq!(examples.answers.get(&2), Some(&answer("288957")));
+
+        let page = PAGE.replace("<pre><code>): 3 points.</code></pre>", "");
+        let examples = Examples::from_html(&page);
+        assert_eq!(examples.inputs.len(), 1);
+        assert_eq!(examples.answers[&1].example, Some(1));
+        assert_eq!(examples.answers[&2].example, Some(1));
+    }
+
+    #[test]
+    fn test_from_html_before_part_two() {
+        let page = PAGE.split("<p>Your puzzle answer").next().unwrap();
+        let examples = Examples::from_html(page);
+        assert_eq!(examples.answers.len(), 1);
+    }
+
+    #[test]
+    fn test_save_skips_blank_blocks() {
+        let root = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        let cache = Cache::new(&root, 2021, "abc123");
+        let examples = Examples::from_html("<pre><code>\n</code></pre><pre><code>1\n</code></pre>");
+        examples.save(&cache, 1).unwrap();
+        assert_eq!(
+            cache.read("day01.example1.txt").unwrap().as_deref(),
+            Some("1\n")
+        );
+        assert_eq!(cache.read("day01.example2.txt").unwrap(), None);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_save_removes_stale_examples() {
+        let root = env::temp_dir().join(format!("aoc-examples-stale-{}", process::id()));
+        let cache = Cache::new(&root, 2021, "abc123");
+        let page = "<pre><code>1\n</code></pre><pre><code>2\n</code></pre>";
+        Examples::from_html(page).save(&cache, 1).unwrap();
+        cache.write("day01.example10.txt", "10\n").unwrap();
+        cache.write("day02.example2.txt", "2\n").unwrap();
+        Examples::from_html("<pre><code>1\n</code></pre>")
+            .save(&cache, 1)
+            .unwrap();
+        let names: Vec<_> = cache.list().unwrap().into_iter().map(|f| f.0).collect();
+        assert_eq!(
+            names,
+            vec![
+                "day01.example1.txt",
+                "day01.examples.json",
+                "day02.example2.txt"
+            ]
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
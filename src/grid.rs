@@ -0,0 +1,418 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{ParseError, Source};
+
+/// A cell's position, as `(x, y)`: the column counting from the left, and the
+/// row counting from the top.
+pub type Pos = (usize, usize);
+
+/// Which of the cells around a cell count as its neighbors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbors {
+    /// The cells above, below, left and right.
+    Four,
+    /// The four cells above, below, left and right, and the four diagonals.
+    Eight,
+}
+
+impl Neighbors {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbors::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighbors::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// A rectangle of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, or returns `None` if they aren't all the
+    /// same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            width,
+        })
+    }
+
+    /// Parses `text`, which must be a slice of `src`'s input, as one row per
+    /// line, turning each character into a cell with `cell`. Characters that
+    /// `cell` rejects are reported as not being `expected`, and every line
+    /// must have as many characters as the first.
+    pub fn parse(
+        src: &Source<'_>,
+        text: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for line in text.lines() {
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                let val = cell(c).ok_or_else(|| src.error(&line[i..i + c.len_utf8()], expected))?;
+                cells.push(val);
+            }
+            let len = cells.len() - start;
+            match width {
+                Some(width) if width != len => {
+                    return Err(src.error(line, format!("a row {} cells wide", width)))
+                }
+                _ => width = Some(len),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the grid has no cells at all.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// The cell at `pos`, or `None` if it's outside the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    /// The cell at `pos` for changing, or `None` if it's outside the grid.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Every cell for changing, along with its position, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// The positions of the neighbors of `pos` that are inside the grid.
+    pub fn neighbors(&self, (x, y): Pos, which: Neighbors) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        which.offsets().iter().filter_map(move |&(dx, dy)| {
+            let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            (pos.0 < width && pos.1 < height).then_some(pos)
+        })
+    }
+
+    /// The rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Chunks can't be empty, but an empty grid has no cells to chunk.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells in row `y`, from left to right.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The cells in column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// The rows of the `width` by `height` rectangle whose top left is at
+    /// `pos`, or `None` if it doesn't fit in the grid.
+    pub fn window(
+        &self,
+        pos: Pos,
+        width: usize,
+        height: usize,
+    ) -> Option<impl Iterator<Item = &[T]>> {
+        let (x, y) = pos;
+        if x + width > self.width || y + height > self.height {
+            return None;
+        }
+        Some((y..y + height).map(move |row| {
+            let start = row * self.width + x;
+            &self.cells[start..start + width]
+        }))
+    }
+
+    /// A grid of the same size with `f` applied to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Lays out `across` by `down` copies of the grid, with each cell in each
+    /// copy made by `f` from the original cell and the position of the copy,
+    /// counting copies from `(0, 0)` at the top left.
+    pub fn tiled(&self, across: usize, down: usize, mut f: impl FnMut(&T, Pos) -> T) -> Grid<T> {
+        let (width, height) = (self.width * across, self.height * down);
+        let cells = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let cell = &self.cells[(y % self.height) * self.width + x % self.width];
+                f(cell, (x / self.width, y / self.height))
+            })
+            .collect();
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `val`.
+    pub fn filled(width: usize, height: usize, val: T) -> Grid<T> {
+        Grid {
+            cells: vec![val; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Swaps the rows and the columns.
+    pub fn transpose(&self) -> Grid<T> {
+        self.rearranged(self.height, self.width, |(x, y)| (y, x))
+    }
+
+    /// Turns the grid a quarter turn clockwise, so that the left column
+    /// becomes the top row.
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        self.rearranged(self.height, self.width, |(x, y)| (y, self.height - 1 - x))
+    }
+
+    /// A `width` by `height` grid whose cell at each position is this grid's
+    /// cell at `source(position)`.
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(Pos) -> Pos) -> Grid<T> {
+        let cells = (0..width * height)
+            .map(|i| self[source((i % width, i / width))].clone())
+            .collect();
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl Grid<u32> {
+    /// Parses a grid of single decimal digits, like a map of heights.
+    pub fn parse_digits(src: &Source<'_>, text: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(src, text, "a digit", |c| c.to_digit(10))
+    }
+}
+
+impl Grid<char> {
+    /// Parses a grid of characters, like a map drawn with `#` and `.`.
+    pub fn parse_chars(src: &Source<'_>, text: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(src, text, "a character", Some)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "position {:?} is outside the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "position {:?} is outside the {}x{} grid",
+                pos, width, height
+            ),
+        }
+    }
+}
+
+/// Writes each row on its own line, with nothing between the cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{Grid, Neighbors};
+    use crate::parse::Source;
+
+    const INPUT: &str = "
+123
+456
+";
+
+    fn grid() -> Grid<u32> {
+        Grid::parse_digits(&Source::new(1, INPUT), INPUT.trim()).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            Grid::parse_chars(&Source::new(1, "#.\n.#"), "#.\n.#")
+                .unwrap()
+                .to_string(),
+            "#.\n.#"
+        );
+
+        let input = "12\n3x\n";
+        let err = Grid::parse_digits(&Source::new(1, input), input).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a digit");
+        let input = "12\n345\n";
+        let err = Grid::parse_digits(&Source::new(1, input), input).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a row 2 cells wide");
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid();
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid.get((0, 1)), Some(&4));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        *grid.get_mut((1, 1)).unwrap() = 0;
+        grid[(0, 0)] += 1;
+        assert_eq!(grid.to_string(), "223\n406");
+        assert_eq!(
+            grid.iter().find(|(_, &val)| val == 6).map(|(pos, _)| pos),
+            Some((2, 1))
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors((0, 0), Neighbors::Four).collect_vec(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors((1, 0), Neighbors::Eight).collect_vec(),
+            vec![(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.neighbors((1, 1), Neighbors::Four).count(), 3);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = grid();
+        assert_eq!(grid.rows().collect_vec(), vec![&[1, 2, 3], &[4, 5, 6]]);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).unwrap().collect_vec(), vec![&2, &5]);
+        assert!(grid.column(3).is_none());
+        assert_eq!(
+            grid.window((1, 0), 2, 2).unwrap().collect_vec(),
+            vec![&[2, 3], &[5, 6]]
+        );
+        assert!(grid.window((2, 0), 2, 1).is_none());
+        assert_eq!(Grid::<u32>::filled(0, 0, 0).rows().count(), 0);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_clockwise().to_string(),
+            "654\n321"
+        );
+        assert_eq!(
+            grid.tiled(2, 2, |&val, (x, y)| val + (x + y) as u32)
+                .to_string(),
+            "123234\n456567\n234345\n567678"
+        );
+        assert_eq!(
+            grid.map(|val| val % 2 == 0).to_string(),
+            "falsetruefalse\ntruefalsetrue"
+        );
+        assert_eq!(
+            Grid::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']]),
+            Some(Grid::parse_chars(&Source::new(1, "ab\ncd"), "ab\ncd").unwrap())
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![2, 3]]), None);
+    }
+}
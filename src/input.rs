@@ -0,0 +1,291 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+use tracing::info;
+
+use crate::{cache::Cache, client::Client, examples, puzzle};
+
+/// Where to read a day's input from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    /// The real puzzle input, downloaded if it isn't cached yet.
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    /// The Nth (1-based) example for the day, from the cached
+    /// `dayNN.exampleN.txt`.
+    Example(usize),
+}
+
+impl InputSource {
+    /// Whether this source gives the same input regardless of the day, so it
+    /// only makes sense to use it for a single day.
+    pub fn is_day_independent(&self) -> bool {
+        matches!(self, InputSource::File(_) | InputSource::Stdin)
+    }
+
+    /// Reads the input for `day`. Files and stdin don't need the cache, so
+    /// `cache` can be `None` for them.
+    pub fn load(&self, cache: Option<&Cache>, day: u32) -> Result<String> {
+        let cache = || cache.ok_or_else(|| anyhow!("reading the {} needs the cache", self));
+        match self {
+            InputSource::Puzzle => get_input(cache()?, day),
+            InputSource::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display())),
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .context("failed to read stdin")?;
+                Ok(text)
+            }
+            InputSource::Example(n) => cache()?
+                .read(&example_name(day, *n))?
+                .ok_or_else(|| anyhow!("no example {} cached for day {}", n, day)),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::File(path) => write!(f, "file {}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Example(n) => write!(f, "example {}", n),
+        }
+    }
+}
+
+/// A subset of an input's lines.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Slice {
+    /// The first N lines.
+    Head(usize),
+    /// Lines `start` to `end` inclusive, numbered from 1.
+    Lines {
+        start: Option<usize>,
+        end: Option<usize>,
+    },
+}
+
+impl Slice {
+    /// Cuts `text` down to the selected lines.
+    pub fn apply(&self, text: &str) -> Result<String> {
+        let lines: Vec<&str> = text.lines().collect();
+        let (start, end) = match *self {
+            Slice::Head(n) => (1, n.min(lines.len())),
+            Slice::Lines { start, end } => {
+                let start = start.unwrap_or(1);
+                let end = end.unwrap_or(lines.len());
+                if start > lines.len() || end > lines.len() {
+                    return Err(anyhow!(
+                        "input only has {} lines, can't slice {}",
+                        lines.len(),
+                        self
+                    ));
+                }
+                (start, end)
+            }
+        };
+        Ok(lines[start - 1..end]
+            .iter()
+            .map(|l| format!("{}\n", l))
+            .collect())
+    }
+}
+
+impl FromStr for Slice {
+    type Err = anyhow::Error;
+
+    /// Parses a line range like "3..7", "3.." or "..7".
+    fn from_str(s: &str) -> Result<Self> {
+        let (start, end) = s
+            .split_once("..")
+            .ok_or_else(|| anyhow!("expected a range like 3..7, got '{}'", s))?;
+        let parse = |n: &str| -> Result<Option<usize>> {
+            if n.is_empty() {
+                return Ok(None);
+            }
+            match n.parse() {
+                Ok(0) | Err(_) => Err(anyhow!("invalid line number '{}'", n)),
+                Ok(n) => Ok(Some(n)),
+            }
+        };
+        let (start, end) = (parse(start)?, parse(end)?);
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(anyhow!("empty line range '{}'", s));
+            }
+        }
+        Ok(Slice::Lines { start, end })
+    }
+}
+
+impl fmt::Display for Slice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bound = |n: Option<usize>| n.map_or(String::new(), |n| n.to_string());
+        match self {
+            Slice::Head(n) => write!(f, "first {} lines", n),
+            Slice::Lines { start, end } => {
+                write!(f, "lines {}..{}", bound(*start), bound(*end))
+            }
+        }
+    }
+}
+
+/// A day's input along with a description of where it came from.
+pub struct Input {
+    pub text: String,
+    pub source: String,
+    /// Known answers by part, for examples whose answers are in the puzzle
+    /// text.
+    pub expected: BTreeMap<u32, String>,
+}
+
+/// Loads the input from `source` and applies `slice` to it, if given. `cache`
+/// is only needed for puzzle inputs and examples.
+pub fn load(
+    cache: Option<&Cache>,
+    source: &InputSource,
+    slice: Option<Slice>,
+    day: u32,
+) -> Result<Input> {
+    let mut text = source.load(cache, day)?;
+    let mut description = source.to_string();
+    let mut expected = BTreeMap::new();
+    match slice {
+        Some(slice) => {
+            text = slice.apply(&text)?;
+            description = format!("{}, {}", description, slice);
+        }
+        None => {
+            if let (InputSource::Example(n), Some(cache)) = (source, cache) {
+                expected = examples::load_answers(cache, day)?
+                    .into_iter()
+                    .filter(|(_, a)| a.example == Some(*n))
+                    .map(|(part, a)| (part, a.answer))
+                    .collect();
+            }
+        }
+    }
+    Ok(Input {
+        text,
+        source: description,
+        expected,
+    })
+}
+
+/// Loads every example of the day that has a known answer, in order.
+pub fn load_examples(cache: &Cache, day: u32) -> Result<Vec<Input>> {
+    let mut numbers: Vec<usize> = examples::load_answers(cache, day)?
+        .values()
+        .filter_map(|a| a.example)
+        .collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+        .into_iter()
+        .map(|n| load(Some(cache), &InputSource::Example(n), None, day))
+        .collect()
+}
+
+/// Loads a day's inputs for a run: each example with a known answer first if
+/// `with_examples` is set, then the input from `source`.
+pub fn load_all(
+    cache: Option<&Cache>,
+    source: &InputSource,
+    slice: Option<Slice>,
+    with_examples: bool,
+    day: u32,
+) -> Result<Vec<Input>> {
+    let mut inputs = match cache {
+        Some(cache) if with_examples => load_examples(cache, day)?,
+        _ => vec![],
+    };
+    inputs.push(load(cache, source, slice, day)?);
+    Ok(inputs)
+}
+
+/// The name of the cached file holding the day's Nth example.
+pub fn example_name(day: u32, n: usize) -> String {
+    format!("day{:02}.example{}.txt", day, n)
+}
+
+/// Reads the day's puzzle input from the cache, downloading it first if
+/// necessary.
+pub fn get_input(cache: &Cache, day: u32) -> Result<String> {
+    let name = format!("day{:02}.txt", day);
+    if let Some(text) = cache.read(&name)? {
+        return Ok(text);
+    }
+    info!(day, "downloading input");
+    let text = Client::shared()?.download_input(cache.year(), day)?;
+    cache.write(&name, &text)?;
+    Ok(text)
+}
+
+/// Downloads and caches the puzzle input for each of `days` that isn't cached
+/// yet, and if `puzzles` is set, their descriptions and examples too. Returns
+/// how many examples were found.
+pub fn fetch(cache: &Cache, days: &[u32], puzzles: bool) -> Result<usize> {
+    for &day in days {
+        get_input(cache, day).with_context(|| format!("failed to fetch day {}", day))?;
+    }
+    let mut examples = 0;
+    if puzzles {
+        for &day in days {
+            examples += puzzle::fetch(cache, day)?;
+        }
+    }
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Slice;
+
+    const INPUT: &str = "a\nb\nc\nd\n";
+
+    #[test]
+    fn test_parse_slice() {
+        assert_eq!(
+            "2..3".parse::<Slice>().unwrap(),
+            Slice::Lines {
+                start: Some(2),
+                end: Some(3)
+            }
+        );
+        assert_eq!(
+            "..3".parse::<Slice>().unwrap(),
+            Slice::Lines {
+                start: None,
+                end: Some(3)
+            }
+        );
+        assert!("3".parse::<Slice>().is_err());
+        assert!("0..3".parse::<Slice>().is_err());
+        assert!("3..2".parse::<Slice>().is_err());
+    }
+
+    #[test]
+    fn test_apply_slice() {
+        assert_eq!(Slice::Head(2).apply(INPUT).unwrap(), "a\nb\n");
+        assert_eq!(Slice::Head(10).apply(INPUT).unwrap(), INPUT);
+        assert_eq!(
+            "2..3".parse::<Slice>().unwrap().apply(INPUT).unwrap(),
+            "b\nc\n"
+        );
+        assert_eq!(
+            "3..".parse::<Slice>().unwrap().apply(INPUT).unwrap(),
+            "c\nd\n"
+        );
+        assert!("3..5".parse::<Slice>().unwrap().apply(INPUT).is_err());
+    }
+}
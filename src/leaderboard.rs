@@ -0,0 +1,341 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::cache::Cache;
+
+/// How often a private leaderboard may be downloaded. The website asks that
+/// the JSON API isn't hit more often than this.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+/// A private leaderboard, as returned by
+/// `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Unset for members who haven't made their name public.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    /// When each star was earned, by day and then part.
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    /// When the star was earned, in seconds since the Unix epoch.
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    /// Parses the JSON that the website serves for a private leaderboard.
+    pub fn from_json(json: &str) -> Result<Leaderboard> {
+        serde_json::from_str(json).context("failed to parse leaderboard")
+    }
+
+    /// The members ordered as on the website: by local score, then by stars,
+    /// then by who earned their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        members
+    }
+
+    /// The last day anyone has earned a star on.
+    fn last_day(&self) -> u32 {
+        self.members
+            .values()
+            .filter_map(|m| m.completion_day_level.keys().max())
+            .max()
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Formats a table of each member's score and stars, followed by how long
+    /// everyone took to solve each day.
+    pub fn render(&self) -> String {
+        let year: i32 = self.event.parse().unwrap_or(0);
+        let ranked = self.ranked();
+        let last_day = self.last_day();
+        let name_width = ranked
+            .iter()
+            .map(|m| m.display_name().chars().count())
+            .chain(["Name".len()])
+            .max()
+            .unwrap();
+
+        let mut out = String::new();
+        let prefix = format!("{:>4}  {:>5}  {:>5}  {:<name_width$}  ", "", "", "", "");
+        if last_day >= 10 {
+            let tens: String = (1..=last_day)
+                .map(|d| {
+                    if d >= 10 {
+                        char::from(b'0' + (d / 10) as u8)
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            writeln!(out, "{}{}", prefix, tens).unwrap();
+        }
+        let units: String = (1..=last_day)
+            .map(|d| char::from(b'0' + (d % 10) as u8))
+            .collect();
+        writeln!(
+            out,
+            "{:>4}  {:>5}  {:>5}  {:<name_width$}  {}",
+            "",
+            "Score",
+            "Stars",
+            "Name",
+            units,
+            name_width = name_width
+        )
+        .unwrap();
+        for (rank, member) in ranked.iter().enumerate() {
+            let stars: String = (1..=last_day)
+                .map(
+                    |day| match member.completion_day_level.get(&day).map(BTreeMap::len) {
+                        Some(2) => '*',
+                        Some(1) => '+',
+                        _ => '.',
+                    },
+                )
+                .collect();
+            writeln!(
+                out,
+                "{:>4}  {:>5}  {:>5}  {:<name_width$}  {}",
+                format!("{})", rank + 1),
+                member.local_score,
+                member.stars,
+                member.display_name(),
+                stars,
+                name_width = name_width
+            )
+            .unwrap();
+        }
+        out.push_str("(* both stars, + first star only)\n");
+
+        for day in 1..=last_day {
+            let mut solvers: Vec<&Member> = ranked
+                .iter()
+                .copied()
+                .filter(|m| m.completion_day_level.contains_key(&day))
+                .collect();
+            // Fastest to finish the whole day first, then fastest to part 1.
+            solvers.sort_by_key(|m| {
+                (
+                    m.completion_time(year, day, 2).unwrap_or(Duration::MAX),
+                    m.completion_time(year, day, 1),
+                )
+            });
+            writeln!(
+                out,
+                "\nDay {:<width$}  {:>8}  {:>8}",
+                day,
+                "Part 1",
+                "Part 2",
+                width = name_width - 2
+            )
+            .unwrap();
+            for member in solvers {
+                let time = |part| {
+                    member
+                        .completion_time(year, day, part)
+                        .map_or("-".to_string(), format_time)
+                };
+                writeln!(
+                    out,
+                    "  {:<name_width$}  {:>8}  {:>8}",
+                    member.display_name(),
+                    time(1),
+                    time(2),
+                    name_width = name_width
+                )
+                .unwrap();
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+impl Member {
+    /// The member's name, or how the website refers to anonymous members.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// How long after the day's puzzle unlocked the member earned the star for
+    /// `part`.
+    pub fn completion_time(&self, year: i32, day: u32, part: u32) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let secs = star.get_star_ts - unlock_time(year, day);
+        Some(Duration::from_secs(secs.max(0) as u64))
+    }
+}
+
+/// Loads a private leaderboard from the cache, calling `download` to refresh
+/// it if there's no cached copy or it's older than `REFRESH_INTERVAL`. Returns
+/// the leaderboard along with how long ago it was downloaded.
+pub fn load(
+    cache: &Cache,
+    id: u64,
+    download: impl FnOnce() -> Result<String>,
+) -> Result<(Leaderboard, Duration)> {
+    let name = format!("leaderboard.{}.json", id);
+    let age = cache.modified(&name)?.map(|modified| {
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default()
+    });
+    let json = match (age, cache.read(&name)?) {
+        (Some(age), Some(json)) if age < REFRESH_INTERVAL => {
+            return Ok((Leaderboard::from_json(&json)?, age))
+        }
+        _ => download().with_context(|| format!("failed to fetch leaderboard {}", id))?,
+    };
+    let leaderboard = Leaderboard::from_json(&json)?;
+    cache.write(&name, &json)?;
+    Ok((leaderboard, Duration::ZERO))
+}
+
+/// When the day's puzzle unlocked, in seconds since the Unix epoch.
+fn unlock_time(year: i32, day: u32) -> i64 {
+    days_from_civil(year as i64, 12, day as i64) * 86400 + UNLOCK_HOUR_UTC * 3600
+}
+
+/// The number of days from 1970-01-01 to the given date, using Howard
+/// Hinnant's algorithm for the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Formats a completion time like the website does, as `HH:MM:SS` or `>24h`.
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs >= 24 * 3600 {
+        return ">24h".to_string();
+    }
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process, time::Duration};
+
+    use anyhow::anyhow;
+
+    use super::{days_from_civil, load, unlock_time, Leaderboard};
+    use crate::cache::Cache;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard.json");
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        // 2021-12-01T05:00:00Z
+        assert_eq!(unlock_time(2021, 1), 1638334800);
+    }
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, "2021");
+        assert_eq!(leaderboard.members.len(), 3);
+        let names: Vec<_> = leaderboard
+            .ranked()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(
+            names,
+            ["Ada Lovelace", "Grace Hopper", "(anonymous user #303)"]
+        );
+        let ada = &leaderboard.members["101"];
+        assert_eq!(
+            ada.completion_time(2021, 1, 2),
+            Some(Duration::from_secs(15 * 60))
+        );
+        assert_eq!(ada.completion_time(2021, 2, 2), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        // Starts with a newline so that the first line's indent is kept.
+        assert_eq!(
+            format!("\n{}", leaderboard.render()),
+            "
+      Score  Stars  Name                   123
+  1)     21      5  Ada Lovelace           *+*
+  2)     21      4  Grace Hopper           **.
+  3)      0      0  (anonymous user #303)  ...
+(* both stars, + first star only)
+
+Day 1                      Part 1    Part 2
+  Ada Lovelace           00:05:00  00:15:00
+  Grace Hopper           00:02:00  00:20:00
+
+Day 2                      Part 1    Part 2
+  Grace Hopper           00:05:00  01:00:00
+  Ada Lovelace           00:01:00         -
+
+Day 3                      Part 1    Part 2
+  Ada Lovelace           22:00:00      >24h"
+        );
+    }
+
+    #[test]
+    fn test_load_refreshes_at_most_every_15_minutes() {
+        let root = env::temp_dir().join(format!("aoc-leaderboard-{}", process::id()));
+        let cache = Cache::new(&root, 2021, "abc123");
+
+        let (leaderboard, age) = load(&cache, 101, || Ok(FIXTURE.to_string())).unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(age, Duration::ZERO);
+        let (leaderboard, _) = load(&cache, 101, || Err(anyhow!("downloaded again"))).unwrap();
+        assert_eq!(leaderboard.owner_id, 101);
+
+        let stale = fs::File::options()
+            .write(true)
+            .open(cache.path("leaderboard.101.json").unwrap())
+            .unwrap();
+        stale
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(16 * 60))
+            .unwrap();
+        assert!(load(&cache, 101, || Err(anyhow!("downloaded again"))).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
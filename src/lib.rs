@@ -0,0 +1,67 @@
+//! Advent of Code solutions, along with the tooling used to fetch inputs and
+//! puzzles, run and check solutions, and submit answers.
+//!
+//! Each event's solutions live in a module named after its year, with one
+//! module per day, e.g. [`y2021::day15`]. A day's module has a unit struct
+//! implementing [`solution::Solution`], whose `parse` turns the raw puzzle
+//! input into the day's input type and whose `part1` and `part2` solve it:
+//!
+//! ```
+//! use aoc_2021::{solution::Solution, y2021::day01::Day01};
+//!
+//! let input = Day01::parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n")?;
+//! assert_eq!(Day01::part1(&input)?, "7");
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Every day also exposes the same steps as free functions: `parse_input`,
+//! which returns a [`parse::ParseError`] pointing at any bad input, and `part1`
+//! and `part2` taking what it returns. Some days have helpers that are useful
+//! on their own too, like
+//! [`y2021::day15::shortest_distance`]. To work with days without knowing them
+//! at compile time, use a [`solution::Registry`].
+
+/// The answer to a single part.
+pub mod answer;
+/// Known-correct answers, for checking solutions for regressions.
+pub mod answers;
+/// Timing parsing and each part over repeated runs.
+pub mod bench;
+/// The on-disk cache of inputs, puzzles and examples.
+pub mod cache;
+/// Command line arguments.
+pub mod cli;
+/// The HTTP client for adventofcode.com.
+pub mod client;
+/// Settings from `aoc.toml` files and the environment.
+pub mod config;
+/// Examples extracted from puzzle descriptions.
+pub mod examples;
+/// Rectangular grids of cells, for puzzles set on a map.
+pub mod grid;
+/// Where a run's input comes from and how it's cut down.
+pub mod input;
+/// Private leaderboards.
+pub mod leaderboard;
+/// Sending tracing output to stderr.
+pub mod logging;
+/// Counting allocations, when built with the `alloc-stats` feature.
+pub mod memory;
+/// Formatting run results.
+pub mod output;
+/// Errors from parsing inputs that point at where they went wrong.
+pub mod parse;
+/// Cached puzzle descriptions and rendering them as text.
+pub mod puzzle;
+/// Running solutions in isolation and collecting their results.
+pub mod runner;
+/// Generating the module for a new day.
+pub mod scaffold;
+/// The `Solution` trait implemented by every day, and the registry of days.
+pub mod solution;
+/// Re-running days against their recorded answers.
+pub mod verify;
+/// Rerunning a day as its input and source change.
+pub mod watch;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
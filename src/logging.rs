@@ -0,0 +1,58 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use anyhow::{Context, Result};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+/// Overrides the filter set by `-v`, using `tracing-subscriber`'s
+/// `EnvFilter` syntax, e.g. `aoc_2021::y2021::day14=debug`.
+const FILTER_ENV_VAR_NAME: &str = "AOC_LOG";
+
+/// The filter to use when `$AOC_LOG` isn't set: warnings by default, then
+/// debug and trace output from this crate for each `-v`.
+fn default_filter(verbosity: u8) -> &'static str {
+    match verbosity {
+        0 => "warn",
+        1 => "warn,aoc_2021=debug",
+        _ => "warn,aoc_2021=trace",
+    }
+}
+
+/// Sends tracing output to stderr, so that it never mixes with the results on
+/// stdout. With `-vv` or more, spans also log how long they took when they
+/// close.
+pub fn init(verbosity: u8) -> Result<()> {
+    let filter = match env::var(FILTER_ENV_VAR_NAME) {
+        Ok(directives) => EnvFilter::try_new(&directives)
+            .with_context(|| format!("invalid ${}", FILTER_ENV_VAR_NAME))?,
+        Err(_) => EnvFilter::new(default_filter(verbosity)),
+    };
+    let span_events = if verbosity >= 2 {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(span_events)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .init();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::EnvFilter;
+
+    use super::default_filter;
+
+    #[test]
+    fn test_default_filters_are_valid() {
+        for verbosity in 0..=3 {
+            assert!(EnvFilter::try_new(default_filter(verbosity)).is_ok());
+        }
+    }
+}
@@ -1,150 +1,310 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-
 use std::{
-    collections::BTreeMap, env, fs, path::Path, process::exit, time::Duration, time::Instant,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process::exit,
+    time::Duration,
 };
 
-use anyhow::{Context, Result};
-
-const YEAR: i32 = 2021;
-static COOKIE_ENV_VAR_NAME: &str = "AOC_SESSION_ID";
-
-#[derive(Eq, Hash, PartialEq)]
-struct Problem {
-    day: i32,
-    part: i32,
-}
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
 
-type PartFunc = fn(&str) -> Result<String>;
-
-/// Hacky macro to slightly simplify the process of importing and using the file
-/// for a new day's problem.
-macro_rules! add_day {
-    ($hm:ident, $module:ident) => {
-        $hm.insert(
-            stringify!($module)
-                .strip_prefix("day")
-                .unwrap()
-                .parse()
-                .unwrap(),
-            ($module::part1, $module::part2),
-        )
-    };
-}
+use aoc_2021::{
+    answer::Answer,
+    answers::{self, AnswerStore},
+    bench,
+    cache::Cache,
+    cli::{self, CacheCommand, Cli, Command, ConfigCommand, Problem},
+    client::{Client, SubmitOutcome},
+    config::Config,
+    input::{self, get_input},
+    leaderboard, logging,
+    parse::ParseError,
+    puzzle, runner,
+    scaffold::Scaffold,
+    solution::Registry,
+    verify, watch,
+};
 
 fn main() {
     match main_impl() {
         Ok(s) => println!("{}", s),
         Err(err) => {
-            println!("{}", err.to_string());
+            eprintln!("error: {:#}", err);
+            if let Some(err) = err.downcast_ref::<ParseError>() {
+                eprintln!("{}", err.snippet());
+            }
             exit(1);
         }
     }
 }
 
 fn main_impl() -> Result<String> {
-    // Use a BTreeMap because it's ordered, so with "all" we can run the
-    // solutions in order.
-    let mut methods: BTreeMap<i32, (PartFunc, PartFunc)> = BTreeMap::new();
-    add_day!(methods, day01);
-    add_day!(methods, day02);
-    add_day!(methods, day03);
-    add_day!(methods, day04);
-    add_day!(methods, day05);
-    add_day!(methods, day06);
-    add_day!(methods, day07);
-    add_day!(methods, day08);
-    add_day!(methods, day09);
-    add_day!(methods, day10);
-    add_day!(methods, day11);
-    add_day!(methods, day12);
-    add_day!(methods, day13);
-    add_day!(methods, day14);
-    add_day!(methods, day15);
-    add_day!(methods, day16);
-    add_day!(methods, day17);
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() == 2 && args[1].eq("all") {
-        let mut total_duration = Duration::ZERO;
-        for (&day, (part1, part2)) in &methods {
-            let input = get_input(day).context("failed to load input")?;
-            println!("Day #{}", day);
-
-            let start = Instant::now();
-            let sol = part1(&input).unwrap();
-            total_duration += start.elapsed();
-            println!("  Part 1: {}", sol);
-
-            let start = Instant::now();
-            let sol = part2(&input).unwrap();
-            total_duration += start.elapsed();
-            println!("  Part 2: {}", sol);
+    let cli = Cli::parse();
+    logging::init(cli.verbose)?;
+    let config = Config::shared()?;
+    let year = match cli.year {
+        Some(year) => year,
+        None => config.year()?,
+    };
+    let registry = Registry::new(year);
 
-            println!();
+    match cli.command {
+        Command::Run {
+            days,
+            part,
+            format,
+            timeout,
+            jobs,
+            input,
+        } => {
+            let days = days.resolve(&registry)?;
+            let (source, slice) = (input.source(), input.slice());
+            if source.is_day_independent() && days.len() > 1 {
+                return Err(anyhow!(
+                    "can't use {} as the input for more than one day",
+                    source
+                ));
+            }
+            // A file or stdin is read as is, without touching the cache.
+            let cache = if source.is_day_independent() {
+                None
+            } else {
+                Some(Cache::from_env(year)?)
+            };
+            let load_inputs =
+                |day| input::load_all(cache.as_ref(), &source, slice, input.with_examples(), day);
+            let options = runner::Options {
+                timeout: solve_timeout(config, timeout),
+                jobs,
+            };
+            runner::run_and_render(
+                &registry,
+                &cli::problems(&days, part),
+                load_inputs,
+                &options,
+                format.unwrap_or_else(|| config.format()),
+            )
+        }
+        Command::Watch { day, part, timeout } => {
+            let day = day.resolve_one(&registry)?;
+            let cache = Cache::from_env(year)?;
+            let options = runner::Options {
+                timeout: solve_timeout(config, timeout),
+                jobs: 1,
+            };
+            watch::watch_day(&registry, &cache, day, part, &options).map(|()| String::new())
         }
-        return Ok(format!(
-            "{} day(s) complete in {:?}!",
-            methods.len(),
-            Duration::from_millis(total_duration.subsec_millis() as u64),
-        ));
-    } else if args.len() != 3 {
-        return Err(anyhow::anyhow!("Usage: aoc <day> <part>"));
+        Command::Fetch { days, puzzle } => {
+            let days = days.resolve(&registry)?;
+            let examples = input::fetch(&Cache::from_env(year)?, &days, puzzle)?;
+            if !puzzle {
+                return Ok(format!("{} input(s) cached", days.len()));
+            }
+            Ok(format!(
+                "{} input(s), puzzle(s) and {} example(s) cached",
+                days.len(),
+                examples
+            ))
+        }
+        Command::Submit { day, part, answer } => {
+            let day = day.resolve_one(&registry)?;
+            let cache = Cache::from_env(year)?;
+            let options = runner::Options {
+                timeout: solve_timeout(config, None),
+                jobs: 1,
+            };
+            let answer = match answer {
+                Some(answer) => answer,
+                None => runner::solve_puzzle(&registry, &cache, Problem { day, part }, &options)?
+                    .submission()?,
+            };
+            let outcome = Client::shared()?.submit(year, day, part, &answer)?;
+            if outcome == SubmitOutcome::Correct {
+                answers::record(&cache, day, part, &answer)?;
+            }
+            Ok(format!(
+                "Day {} part {}: {} is {}",
+                day, part, answer, outcome
+            ))
+        }
+        Command::Record { day, part, answer } => {
+            let day = day.resolve_one(&registry)?;
+            let cache = Cache::from_env(year)?;
+            let options = runner::Options {
+                timeout: solve_timeout(config, None),
+                jobs: 1,
+            };
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    match runner::solve_puzzle(&registry, &cache, Problem { day, part }, &options)?
+                    {
+                        Answer::Unsolved => {
+                            return Err(anyhow!("day {} part {} isn't solved yet", day, part))
+                        }
+                        answer => answer.to_string(),
+                    }
+                }
+            };
+            match answers::record(&cache, day, part, &answer)? {
+                Some(old) if old != answer => Ok(format!(
+                    "Day {} part {}: recorded {} (replacing {})",
+                    day, part, answer, old
+                )),
+                _ => Ok(format!("Day {} part {}: recorded {}", day, part, answer)),
+            }
+        }
+        Command::Verify { days, junit } => {
+            let days = days.resolve(&registry)?;
+            let cache = Cache::from_env(year)?;
+            let store = AnswerStore::open(&cache)?;
+            let timeout = solve_timeout(config, None);
+            let results = verify::verify(
+                &registry,
+                &days,
+                &store,
+                |day| get_input(&cache, day),
+                timeout,
+            );
+            if let Some(path) = junit {
+                fs::write(&path, verify::to_junit(&results))
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+            }
+            let report = verify::report(&results);
+            match verify::counts(&results) {
+                (_, 0, 0, _, _) => Ok(report),
+                (_, failed, errors, _, _) => {
+                    println!("{}", report);
+                    Err(anyhow!(
+                        "verification failed: {} mismatch(es), {} error(s)",
+                        failed,
+                        errors
+                    ))
+                }
+            }
+        }
+        Command::Bench {
+            days,
+            warmup,
+            iterations,
+            save,
+            compare,
+            threshold,
+        } => {
+            let days = days.resolve(&registry)?;
+            let cache = Cache::from_env(year)?;
+            let options = bench::Options { warmup, iterations };
+            let results =
+                bench::bench_days(&registry, &days, |day| get_input(&cache, day), &options)?;
+            let report = bench::report(&results);
+            if let Some(path) = save {
+                bench::save_baseline(&path, &results)?;
+            }
+            let regressions = match compare {
+                Some(path) => {
+                    let baseline = bench::load_baseline(&path)?;
+                    bench::regressions(&results, &baseline, threshold / 100.0)
+                }
+                None => vec![],
+            };
+            if regressions.is_empty() {
+                return Ok(report);
+            }
+            println!("{}\n\nRegressions:", report);
+            for r in &regressions {
+                println!("  {}", r);
+            }
+            Err(anyhow!("{} regression(s) found", regressions.len()))
+        }
+        Command::Show {
+            day,
+            markdown,
+            width,
+        } => {
+            let html = puzzle::load(&Cache::from_env(year)?, day)?;
+            if markdown {
+                Ok(puzzle::to_markdown(&html))
+            } else {
+                Ok(puzzle::to_text(&html, width, io::stdout().is_terminal()))
+            }
+        }
+        Command::Leaderboard { id } => {
+            let cache = Cache::from_env(year)?;
+            let (leaderboard, age) = leaderboard::load(&cache, id, || {
+                Client::shared()?.download_leaderboard(year, id)
+            })?;
+            Ok(format!(
+                "Leaderboard {} for {}, updated {} minute(s) ago\n\n{}",
+                id,
+                year,
+                age.as_secs() / 60,
+                leaderboard.render()
+            ))
+        }
+        Command::Cache { command } => {
+            let cache = Cache::from_env(year)?;
+            match command {
+                CacheCommand::List => cache.report(),
+                CacheCommand::Verify => {
+                    let problems = cache.verify()?;
+                    if problems.is_empty() {
+                        return Ok(format!("{}: all files ok", cache.dir()?.display()));
+                    }
+                    for (name, problem) in &problems {
+                        println!("{}: {}", name, problem);
+                    }
+                    Err(anyhow!(
+                        "{} invalid file(s), remove them with `cache clear`",
+                        problems.len()
+                    ))
+                }
+                CacheCommand::Clear { day } => {
+                    let removed = cache.clear(day)?;
+                    Ok(format!("removed {} file(s)", removed))
+                }
+            }
+        }
+        Command::Config { command } => match command {
+            ConfigCommand::Show => Ok(config.show()),
+        },
+        Command::New { day, offline } => {
+            let scaffold = if offline {
+                Scaffold {
+                    year,
+                    day,
+                    title: None,
+                    example: None,
+                    answers: Default::default(),
+                }
+            } else {
+                Scaffold::from_puzzle(&Cache::from_env(year)?, day)?
+            };
+            let path = scaffold.create(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))?;
+            Ok(format!("created {}", path.display()))
+        }
+        Command::List => Ok(list_days(&registry)),
     }
-    let day: i32 = args[1].parse().unwrap();
-    let parts = methods.get(&day).expect("unimplemented day");
-    let part: i32 = args[2].parse().unwrap();
-    let meth = if part == 1 {
-        parts.0
-    } else if part == 2 {
-        parts.1
-    } else {
-        return Err(anyhow::anyhow!("invalid part {}", part));
-    };
-
-    let input = get_input(day).context("failed to load input")?;
-    meth(&input)
 }
 
-fn get_input(day: i32) -> Result<String> {
-    let cache_dir = Path::new("./.cache");
-    if !cache_dir.exists() {
-        fs::create_dir(cache_dir)?;
-    }
-    let day_file = cache_dir.join(format!("day{:02}.txt", day));
-    if day_file.exists() {
-        return fs::read_to_string(day_file).context("failed to write to cache");
+/// The timeout given on the command line, or else the configured one, with 0
+/// meaning no limit.
+fn solve_timeout(config: &Config, timeout: Option<u64>) -> Option<Duration> {
+    match timeout {
+        Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs)),
+        None => config.solve_timeout(),
     }
-    let text = download_input(day)?;
-    std::fs::write(day_file, text.as_str())?;
-    Ok(text)
 }
 
-fn download_input(day: i32) -> Result<String> {
-    let cookie =
-        env::var(COOKIE_ENV_VAR_NAME).context(format!("${} must be set", COOKIE_ENV_VAR_NAME))?;
-    let url = format!("https://adventofcode.com/{}/day/{}/input", YEAR, day);
-    let client = reqwest::blocking::ClientBuilder::new().build()?;
-    let resp = client
-        .get(url)
-        .header(reqwest::header::COOKIE, format!("session={}", cookie))
-        .send()?
-        .error_for_status()?;
-    resp.text().context("failed to decode response")
+/// Lists every registered day along with its title and any notes.
+fn list_days(registry: &Registry) -> String {
+    registry
+        .iter()
+        .map(|s| match s.metadata().notes {
+            Some(notes) => format!("{:>2}: {} ({})", s.day(), s.title(), notes),
+            None => format!("{:>2}: {}", s.day(), s.title()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
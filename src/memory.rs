@@ -0,0 +1,214 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Whether allocations are being counted, i.e. whether the crate was built with
+/// the `alloc-stats` feature.
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+/// The allocations made while running one phase of a solution.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// How many allocations were made, counting each reallocation as one.
+    pub count: u64,
+    /// The total size of all the allocations, in bytes.
+    pub bytes: u64,
+    /// The most memory that was allocated and not yet freed at any one time,
+    /// in bytes, on top of what was already allocated when the phase started.
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Runs `f`, counting the allocations it makes on the current thread. Returns
+/// `None` for the counts if the crate wasn't built with the `alloc-stats`
+/// feature.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let start = counting::start();
+        let val = f();
+        (val, Some(counting::since(start)))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+/// Formats a size with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: counting::Counting = counting::Counting;
+
+/// A global allocator that hands everything to the system allocator, keeping
+/// per-thread counts as it goes. Solutions run on a single thread, so counting
+/// per thread attributes allocations to the right phase even when several days
+/// run at once, without the cost of atomics.
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::AllocStats;
+
+    thread_local! {
+        static COUNT: Cell<u64> = const { Cell::new(0) };
+        static BYTES: Cell<u64> = const { Cell::new(0) };
+        /// Can go negative when memory allocated on another thread is freed
+        /// on this one.
+        static LIVE: Cell<i64> = const { Cell::new(0) };
+        static PEAK: Cell<i64> = const { Cell::new(0) };
+    }
+
+    pub struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    // The counters can't be reached once the thread's locals have been torn
+    // down, in which case the allocation just goes uncounted.
+
+    fn allocated(size: usize) {
+        let _ = COUNT.try_with(|c| c.set(c.get() + 1));
+        let _ = BYTES.try_with(|b| b.set(b.get() + size as u64));
+        if let Ok(live) = LIVE.try_with(|l| {
+            l.set(l.get() + size as i64);
+            l.get()
+        }) {
+            let _ = PEAK.try_with(|p| p.set(p.get().max(live)));
+        }
+    }
+
+    fn freed(size: usize) {
+        let _ = LIVE.try_with(|l| l.set(l.get() - size as i64));
+    }
+
+    /// The counters when measuring started.
+    pub struct Start {
+        count: u64,
+        bytes: u64,
+        live: i64,
+    }
+
+    pub fn start() -> Start {
+        let live = LIVE.with(Cell::get);
+        PEAK.with(|p| p.set(live));
+        Start {
+            count: COUNT.with(Cell::get),
+            bytes: BYTES.with(Cell::get),
+            live,
+        }
+    }
+
+    pub fn since(start: Start) -> AllocStats {
+        AllocStats {
+            count: COUNT.with(Cell::get) - start.count,
+            bytes: BYTES.with(Cell::get) - start.bytes,
+            peak_bytes: (PEAK.with(Cell::get) - start.live).max(0) as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, measure, AllocStats};
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(
+            AllocStats {
+                count: 3,
+                bytes: 2048,
+                peak_bytes: 100,
+            }
+            .to_string(),
+            "3 allocs, 2.0 KiB allocated, 100 B peak"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn test_measure() {
+        let (len, stats) = measure(|| {
+            let first = vec![1u8; 1000];
+            drop(first);
+            let second: Vec<u8> = Vec::with_capacity(500);
+            second.capacity()
+        });
+        assert_eq!(len, 500);
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                count: 2,
+                bytes: 1500,
+                peak_bytes: 1000,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn test_measure() {
+        assert_eq!(measure(|| 7), (7, None));
+    }
+}
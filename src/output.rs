@@ -0,0 +1,499 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answer::Answer,
+    input::InputSource,
+    memory::{format_bytes, AllocStats},
+    runner::{ParseResult, PartResult, Report, Status, Summary},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Human-readable, one line per part.
+    Plain,
+    /// Aligned columns, with a row per part and one per parse.
+    Table,
+    /// A JSON object with `results`, `parses` and `summary` fields.
+    Json,
+    /// One row per part and one per parse, plus a final `total` row.
+    Csv,
+}
+
+/// Formats the results of a run.
+pub fn render(format: Format, report: &Report) -> String {
+    match format {
+        Format::Plain => plain(report),
+        Format::Table => table(report),
+        Format::Json => json(report),
+        Format::Csv => csv(report),
+    }
+}
+
+/// Formats a run of a single part on a single input: just the answer for the
+/// puzzle input, or else the answer under a line saying where the input came
+/// from, like plain output without the summary.
+pub fn single(report: &Report) -> String {
+    match &report.results[..] {
+        [r] if r.input_source == Some(InputSource::Puzzle.to_string()) => answer_or_error(r),
+        _ => plain_results(report).trim_end().to_string(),
+    }
+}
+
+fn plain(report: &Report) -> String {
+    let mut out = plain_results(report);
+    out.push('\n');
+    out.push_str(&summary_line(&report.summary()));
+    out
+}
+
+/// Each day's results in plain format, without the summary.
+fn plain_results(report: &Report) -> String {
+    let mut out = String::new();
+    let mut prev = None;
+    for r in &report.results {
+        if prev != Some((r.day, &r.input_source)) {
+            if prev.is_some() {
+                out.push('\n');
+            }
+            match &r.input_source {
+                Some(source) => writeln!(out, "Day #{}: {} ({})", r.day, r.title, source),
+                None => writeln!(out, "Day #{}: {}", r.day, r.title),
+            }
+            .unwrap();
+            let parse_alloc = parse_of(report, r).and_then(|p| p.alloc);
+            if let Some(alloc) = parse_alloc {
+                writeln!(out, "  Parse: [{}]", alloc).unwrap();
+            }
+            prev = Some((r.day, &r.input_source));
+        }
+        let answer = answer_or_error(r);
+        let alloc = r.alloc.map_or(String::new(), |a| format!("  [{}]", a));
+        if answer.contains('\n') {
+            // Grids go on their own lines so that they line up.
+            writeln!(out, "  Part {}:{}", r.part, alloc).unwrap();
+            for line in answer.lines() {
+                writeln!(out, "    {}", line).unwrap();
+            }
+        } else {
+            writeln!(out, "  Part {}: {}{}", r.part, answer, alloc).unwrap();
+        }
+    }
+    out
+}
+
+fn table(report: &Report) -> String {
+    // Allocation columns are only shown when allocations are being counted.
+    let counted = report.results.iter().any(|r| r.alloc.is_some());
+    let mut header = vec!["Day", "Part", "Status", "Duration"];
+    if counted {
+        header.extend(["Allocs", "Allocated", "Peak"]);
+    }
+    header.extend(["Input", "Answer"]);
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut prev = None;
+    for r in &report.results {
+        // Each input's parse gets a row of its own, ahead of its parts.
+        if prev != Some((r.day, &r.input_source)) {
+            if let Some(p) = parse_of(report, r) {
+                let mut row = vec![
+                    p.day.to_string(),
+                    "parse".to_string(),
+                    status_name(p.status).to_string(),
+                    format!("{:.2?}", p.duration),
+                ];
+                if counted {
+                    row.extend(alloc_fields(p.alloc, true));
+                }
+                row.extend([p.input_source.clone(), String::new()]);
+                rows.push(row);
+            }
+            prev = Some((r.day, &r.input_source));
+        }
+        let mut row = vec![
+            r.day.to_string(),
+            r.part.to_string(),
+            status_name(r.status).to_string(),
+            format!("{:.2?}", r.duration),
+        ];
+        if counted {
+            row.extend(alloc_fields(r.alloc, true));
+        }
+        row.extend([r.input_source.clone().unwrap_or_default(), table_answer(r)]);
+        rows.push(row);
+    }
+    let left_aligned = [2, header.len() - 2, header.len() - 1];
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut out = String::new();
+    let header: Vec<String> = header.into_iter().map(String::from).collect();
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| {
+                // Left-align the free-text columns, right-align the rest.
+                if left_aligned.contains(&i) {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end()).unwrap();
+    }
+    out.push('\n');
+    out.push_str(&summary_line(&report.summary()));
+    out
+}
+
+fn json(report: &Report) -> String {
+    #[derive(Serialize)]
+    struct Output<'a> {
+        results: &'a [PartResult],
+        parses: &'a [ParseResult],
+        summary: Summary,
+    }
+    serde_json::to_string_pretty(&Output {
+        results: &report.results,
+        parses: &report.parses,
+        summary: report.summary(),
+    })
+    .expect("failed to serialize results")
+}
+
+fn csv(report: &Report) -> String {
+    let mut out = String::from(
+        "day,part,title,status,answer,expected,error,duration_ns,input_hash,input_source,\
+         allocs,alloc_bytes,peak_bytes\n",
+    );
+    let mut prev = None;
+    for r in &report.results {
+        if prev != Some((r.day, &r.input_source)) {
+            if let Some(p) = parse_of(report, r) {
+                let fields = [
+                    p.day.to_string(),
+                    "parse".to_string(),
+                    r.title.to_string(),
+                    status_name(p.status).to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    p.duration.as_nanos().to_string(),
+                    r.input_hash.clone().unwrap_or_default(),
+                    p.input_source.clone(),
+                ]
+                .into_iter()
+                .chain(alloc_fields(p.alloc, false))
+                .collect::<Vec<_>>();
+                writeln!(out, "{}", csv_row(&fields)).unwrap();
+            }
+            prev = Some((r.day, &r.input_source));
+        }
+        let fields = [
+            r.day.to_string(),
+            r.part.to_string(),
+            r.title.to_string(),
+            status_name(r.status).to_string(),
+            r.answer.as_ref().map(Answer::to_string).unwrap_or_default(),
+            r.expected.clone().unwrap_or_default(),
+            r.error.clone().unwrap_or_default(),
+            r.duration.as_nanos().to_string(),
+            r.input_hash.clone().unwrap_or_default(),
+            r.input_source.clone().unwrap_or_default(),
+        ]
+        .into_iter()
+        .chain(alloc_fields(r.alloc, false))
+        .collect::<Vec<_>>();
+        writeln!(out, "{}", csv_row(&fields)).unwrap();
+    }
+    let summary = report.summary();
+    let (status, error) = if summary.succeeded() {
+        (Status::Ok, String::new())
+    } else {
+        (Status::Error, counts(&summary))
+    };
+    let fields = [
+        "total".to_string(),
+        String::new(),
+        String::new(),
+        status_name(status).to_string(),
+        String::new(),
+        String::new(),
+        error,
+        summary.total_duration.as_nanos().to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ];
+    write!(out, "{}", csv_row(&fields)).unwrap();
+    out
+}
+
+/// The parse of the input that `r` was run on, if it was recorded.
+fn parse_of<'a>(report: &'a Report, r: &PartResult) -> Option<&'a ParseResult> {
+    report
+        .parses
+        .iter()
+        .find(|p| p.day == r.day && Some(&p.input_source) == r.input_source.as_ref())
+}
+
+fn csv_row(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|f| {
+            if f.contains([',', '"', '\n']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The allocation count, bytes allocated and peak bytes, formatted for people
+/// or as plain numbers, or empty if allocations weren't counted.
+fn alloc_fields(alloc: Option<AllocStats>, human: bool) -> [String; 3] {
+    match alloc {
+        Some(a) if human => [
+            a.count.to_string(),
+            format_bytes(a.bytes),
+            format_bytes(a.peak_bytes),
+        ],
+        Some(a) => [
+            a.count.to_string(),
+            a.bytes.to_string(),
+            a.peak_bytes.to_string(),
+        ],
+        None => Default::default(),
+    }
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Ok => "ok",
+        Status::Error => "error",
+        Status::Panic => "panic",
+        Status::Timeout => "timeout",
+        Status::Mismatch => "mismatch",
+        Status::Unsolved => "unsolved",
+    }
+}
+
+/// Fits the answer or error on one line, leaving out any context under an
+/// error.
+fn table_answer(r: &PartResult) -> String {
+    let answer = answer_or_error(r);
+    match r.answer {
+        Some(_) => answer.replace('\n', "\\n"),
+        None => answer.lines().next().unwrap_or_default().to_string(),
+    }
+}
+
+fn answer_or_error(r: &PartResult) -> String {
+    match (&r.answer, &r.error) {
+        (Some(answer), _) if r.status == Status::Mismatch => format!(
+            "MISMATCH: got {}, expected {}",
+            answer,
+            r.expected.as_deref().unwrap_or("")
+        ),
+        (Some(answer), _) => answer.to_string(),
+        (None, Some(err)) => format!("{}: {}", status_name(r.status).to_uppercase(), err),
+        (None, None) => status_name(r.status).to_uppercase(),
+    }
+}
+
+fn summary_line(summary: &Summary) -> String {
+    if summary.passed == summary.parts {
+        format!(
+            "{} day(s) complete in {:?}!",
+            summary.days, summary.total_duration
+        )
+    } else {
+        format!(
+            "{} day(s) run in {:?}: {}",
+            summary.days,
+            summary.total_duration,
+            counts(summary)
+        )
+    }
+}
+
+fn counts(summary: &Summary) -> String {
+    let mut counts = format!(
+        "{} passed, {} failed, {} timed out",
+        summary.passed, summary.failed, summary.timed_out
+    );
+    if summary.unsolved > 0 {
+        write!(counts, ", {} unsolved", summary.unsolved).unwrap();
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render, Format};
+    use crate::{
+        answer::Answer,
+        memory::AllocStats,
+        runner::{ParseResult, PartResult, Report, Status},
+    };
+
+    fn report() -> Report {
+        Report {
+            results: vec![
+                PartResult {
+                    day: 13,
+                    part: 1,
+                    title: "Transparent Origami",
+                    status: Status::Ok,
+                    answer: Some(Answer::Unsigned(17)),
+                    expected: Some("17".to_string()),
+                    error: None,
+                    duration: Duration::from_micros(5),
+                    input_hash: Some("abc".to_string()),
+                    input_source: Some("example 1".to_string()),
+                    alloc: None,
+                },
+                PartResult {
+                    day: 13,
+                    part: 2,
+                    title: "Transparent Origami",
+                    status: Status::Error,
+                    answer: None,
+                    expected: None,
+                    error: Some("bad fold, \"z\"".to_string()),
+                    duration: Duration::from_micros(1),
+                    input_hash: Some("abc".to_string()),
+                    input_source: Some("example 1".to_string()),
+                    alloc: None,
+                },
+            ],
+            parses: vec![ParseResult {
+                day: 13,
+                input_source: "example 1".to_string(),
+                status: Status::Ok,
+                duration: Duration::from_micros(4),
+                alloc: None,
+            }],
+            total_duration: Duration::from_micros(10),
+        }
+    }
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(
+            render(Format::Plain, &report()),
+            "\
+Day #13: Transparent Origami (example 1)
+  Part 1: 17
+  Part 2: ERROR: bad fold, \"z\"
+
+1 day(s) run in 10µs: 1 passed, 1 failed, 0 timed out"
+        );
+    }
+
+    #[test]
+    fn test_plain_grid() {
+        let mut report = report();
+        report.results[1].status = Status::Ok;
+        report.results[1].answer = Some(Answer::grid([(0, 0), (1, 1)]));
+        assert!(render(Format::Plain, &report).contains("  Part 2:\n    #.\n    .#\n"));
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            render(Format::Table, &report()),
+            "\
+Day   Part  Status  Duration  Input      Answer
+ 13  parse  ok        4.00µs  example 1
+ 13      1  ok        5.00µs  example 1  17
+ 13      2  error     1.00µs  example 1  ERROR: bad fold, \"z\"
+
+1 day(s) run in 10µs: 1 passed, 1 failed, 0 timed out"
+        );
+    }
+
+    #[test]
+    fn test_alloc_stats() {
+        let alloc = AllocStats {
+            count: 3,
+            bytes: 2048,
+            peak_bytes: 1024,
+        };
+        let mut report = report();
+        report.results[0].alloc = Some(alloc);
+        report.results[1].alloc = Some(AllocStats::default());
+        report.parses[0].alloc = Some(alloc);
+        assert!(render(Format::Plain, &report).starts_with(
+            "\
+Day #13: Transparent Origami (example 1)
+  Parse: [3 allocs, 2.0 KiB allocated, 1.0 KiB peak]
+  Part 1: 17  [3 allocs, 2.0 KiB allocated, 1.0 KiB peak]
+"
+        ));
+        assert!(render(Format::Table, &report).starts_with(
+            "\
+Day   Part  Status  Duration  Allocs  Allocated     Peak  Input      Answer
+ 13  parse  ok        4.00µs       3    2.0 KiB  1.0 KiB  example 1
+ 13      1  ok        5.00µs       3    2.0 KiB  1.0 KiB  example 1  17
+"
+        ));
+        assert!(render(Format::Csv, &report).contains(",5000,abc,example 1,3,2048,1024\n"));
+        let value: serde_json::Value =
+            serde_json::from_str(&render(Format::Json, &report)).unwrap();
+        assert_eq!(
+            value["results"][0]["alloc"],
+            serde_json::json!({"count": 3, "bytes": 2048, "peak_bytes": 1024})
+        );
+        assert_eq!(value["parses"][0]["alloc"]["count"], 3);
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            render(Format::Csv, &report()),
+            "\
+day,part,title,status,answer,expected,error,duration_ns,input_hash,input_source,allocs,alloc_bytes,peak_bytes
+13,parse,Transparent Origami,ok,,,,4000,abc,example 1,,,
+13,1,Transparent Origami,ok,17,17,,5000,abc,example 1,,,
+13,2,Transparent Origami,error,,,\"bad fold, \"\"z\"\"\",1000,abc,example 1,,,
+total,,,error,,,\"1 passed, 1 failed, 0 timed out\",10000,,,,,"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let value: serde_json::Value =
+            serde_json::from_str(&render(Format::Json, &report())).unwrap();
+        assert_eq!(
+            value["results"][0]["answer"],
+            serde_json::json!({"kind": "unsigned", "value": 17})
+        );
+        assert_eq!(value["results"][0]["duration_ns"], 5000);
+        assert_eq!(value["results"][0]["input_source"], "example 1");
+        assert_eq!(value["results"][1]["status"], "error");
+        assert_eq!(value["results"][1]["answer"], serde_json::Value::Null);
+        assert_eq!(value["parses"][0]["duration_ns"], 4000);
+        assert_eq!(value["summary"]["failed"], 1);
+        assert_eq!(value["summary"]["total_duration_ns"], 10000);
+    }
+}
@@ -0,0 +1,164 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Where a day's input stopped making sense, and what was expected there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    /// The line the offending text starts on, numbered from 1.
+    pub line: usize,
+    /// The column the offending text starts at, in characters, numbered from
+    /// 1.
+    pub column: usize,
+    /// The offending text, which is empty if something was missing.
+    pub found: String,
+    /// What should have been there instead, e.g. "a number".
+    pub expected: String,
+    /// The whole of the line the offending text starts on.
+    pub context: String,
+}
+
+impl ParseError {
+    /// The offending line, with a caret under the offending text:
+    ///
+    /// ```text
+    ///   |
+    /// 3 | 1,x,3
+    ///   |   ^
+    /// ```
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Only the part of the offending text on its first line is underlined.
+        let width = self
+            .found
+            .lines()
+            .next()
+            .map_or(0, |first| first.chars().count())
+            .max(1);
+        format!(
+            "{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            number,
+            self.context,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A day's input, for making errors that point into it.
+#[derive(Clone, Copy, Debug)]
+pub struct Source<'a> {
+    day: u32,
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    /// The whole of the input for `day`, as given to the day's parser.
+    pub fn new(day: u32, text: &'a str) -> Source<'a> {
+        Source { day, text }
+    }
+
+    /// An error for `span`, which must be a slice of the input, so that its
+    /// position can be worked out. Trimming or splitting the input keeps it a
+    /// slice; copying it into a `String` doesn't.
+    pub fn error(&self, span: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (span.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        debug_assert!(
+            offset <= self.text.len(),
+            "span {:?} isn't part of the input",
+            span
+        );
+        let offset = if offset <= self.text.len() { offset } else { 0 };
+        let line_start = self.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            day: self.day,
+            line: self.text[..offset].matches('\n').count() + 1,
+            column: self.text[line_start..offset].chars().count() + 1,
+            found: span.to_string(),
+            expected: expected.into(),
+            context: self.text[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// An error for something missing straight after `span`, which must be a
+    /// slice of the input.
+    pub fn error_after(&self, span: &str, expected: impl Into<String>) -> ParseError {
+        self.error(&span[span.len()..], expected)
+    }
+
+    /// Parses `s`, which must be a slice of the input, as a number.
+    pub fn number<T: FromStr>(&self, s: &str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error(s, "a number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Source;
+
+    const INPUT: &str = "
+12
+3x4
+";
+
+    #[test]
+    fn test_error_position() {
+        let src = Source::new(1, INPUT);
+        let line = INPUT.trim().lines().nth(1).unwrap();
+        let err = src.error(&line[1..2], "a digit");
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.found, "x");
+        assert_eq!(err.context, "3x4");
+        assert_eq!(
+            err.to_string(),
+            "day 1 input, line 3, column 2: expected a digit, found \"x\""
+        );
+        assert_eq!(err.snippet(), "  |\n3 | 3x4\n  |  ^");
+    }
+
+    #[test]
+    fn test_number() {
+        let src = Source::new(7, INPUT);
+        let line = INPUT.trim().lines().next().unwrap();
+        assert_eq!(src.number::<u32>(line), Ok(12));
+        let err = src.number::<u32>(INPUT.trim()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a number");
+        assert_eq!(err.snippet(), "  |\n2 | 12\n  | ^^");
+    }
+
+    #[test]
+    fn test_error_after() {
+        let src = Source::new(2, INPUT);
+        let err = src.error_after(INPUT.trim(), "another line");
+        assert_eq!((err.line, err.column), (3, 4));
+        assert_eq!(
+            err.to_string(),
+            "day 2 input, line 3, column 4: expected another line, found nothing"
+        );
+        assert_eq!(err.snippet(), "  |\n3 | 3x4\n  |    ^");
+    }
+}
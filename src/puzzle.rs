@@ -0,0 +1,351 @@
+use std::{fmt::Write, mem};
+
+use anyhow::{anyhow, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{cache::Cache, client::Client, examples::Examples};
+
+lazy_static! {
+    static ref ARTICLE_RE: Regex =
+        Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+    static ref TITLE_RE: Regex = Regex::new(r"--- Day \d+: (.+?) ---").unwrap();
+    static ref TOKEN_RE: Regex =
+        Regex::new(r#"<(/?)(\w+)(?:[^>]*?href="([^"]*)")?[^>]*>|[^<]+"#).unwrap();
+}
+
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const UNDERLINE: &str = "\x1b[4m";
+const RESET: &str = "\x1b[0m";
+
+/// Extracts the inner HTML of each part's description from a puzzle page.
+pub fn articles(html: &str) -> Vec<&str> {
+    ARTICLE_RE
+        .captures_iter(html)
+        .map(|caps| caps.get(1).unwrap().as_str())
+        .collect()
+}
+
+/// Extracts the puzzle's title, e.g. "Sonar Sweep".
+pub fn title(html: &str) -> Option<String> {
+    TITLE_RE.captures(html).map(|caps| unescape(&caps[1]))
+}
+
+/// Caches the descriptions from a puzzle page as `dayNN.puzzle.html`.
+pub fn save(cache: &Cache, day: u32, page: &str) -> Result<()> {
+    let html: String = articles(page)
+        .into_iter()
+        .map(|a| format!("<article class=\"day-desc\">{}</article>\n", a))
+        .collect();
+    cache.write(&name(day), &html)
+}
+
+/// Downloads and caches the day's puzzle description and examples, returning
+/// how many examples were found.
+pub fn fetch(cache: &Cache, day: u32) -> Result<usize> {
+    let page = Client::shared()?
+        .download_puzzle(cache.year(), day)
+        .with_context(|| format!("failed to fetch day {} puzzle", day))?;
+    save(cache, day, &page)?;
+    let examples = Examples::from_html(&page);
+    examples.save(cache, day)?;
+    Ok(examples.inputs.len())
+}
+
+/// Loads the cached descriptions for the day.
+pub fn load(cache: &Cache, day: u32) -> Result<String> {
+    cache.read(&name(day))?.ok_or_else(|| {
+        anyhow!(
+            "no cached puzzle for day {}, run `fetch {} --puzzle` first",
+            day,
+            day
+        )
+    })
+}
+
+fn name(day: u32) -> String {
+    format!("day{:02}.puzzle.html", day)
+}
+
+#[derive(Clone, Default)]
+struct Style {
+    code: bool,
+    em: bool,
+    link: Option<String>,
+}
+
+struct Span {
+    text: String,
+    style: Style,
+}
+
+enum Block {
+    Heading(Vec<Span>),
+    Paragraph(Vec<Span>),
+    ListItem(Vec<Span>),
+    Code(String),
+}
+
+/// Parses the subset of HTML used in puzzle descriptions into blocks of
+/// styled text, ignoring anything it doesn't know about.
+fn parse(html: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut spans: Vec<Span> = vec![];
+    let mut style = Style::default();
+    let mut pre: Option<String> = None;
+    for caps in TOKEN_RE.captures_iter(html) {
+        let tag = match caps.get(2) {
+            Some(tag) => tag.as_str(),
+            None => {
+                let text = unescape(&caps[0]);
+                match &mut pre {
+                    Some(code) => code.push_str(&text),
+                    None => spans.push(Span {
+                        text,
+                        style: style.clone(),
+                    }),
+                }
+                continue;
+            }
+        };
+        let closing = !caps[1].is_empty();
+        match (tag, closing) {
+            ("pre", false) => pre = Some(String::new()),
+            ("pre", true) => blocks.extend(pre.take().map(Block::Code)),
+            (_, _) if pre.is_some() => {}
+            ("code", open) => style.code = !open,
+            ("em", open) => style.em = !open,
+            ("a", false) => style.link = caps.get(3).map(|m| unescape(m.as_str())),
+            ("a", true) => style.link = None,
+            ("h2", true) => blocks.push(Block::Heading(mem::take(&mut spans))),
+            ("p", true) => blocks.push(Block::Paragraph(mem::take(&mut spans))),
+            ("li", true) => blocks.push(Block::ListItem(mem::take(&mut spans))),
+            ("h2" | "p" | "li", false) => spans.clear(),
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Replaces the HTML entities that puzzle pages use with the characters they
+/// stand for.
+pub(crate) fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Renders cached descriptions as Markdown.
+pub fn to_markdown(html: &str) -> String {
+    let inline = |spans: &[Span]| -> String {
+        let text: String = spans
+            .iter()
+            .map(|s| {
+                let mut text = s.text.clone();
+                if s.style.code {
+                    text = format!("`{}`", text);
+                }
+                if s.style.em {
+                    text = format!("**{}**", text);
+                }
+                match &s.style.link {
+                    Some(href) => format!("[{}]({})", text, href),
+                    None => text,
+                }
+            })
+            .collect();
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    let blocks: Vec<String> = parse(html)
+        .iter()
+        .map(|block| match block {
+            Block::Heading(spans) => format!("## {}", inline(spans).trim_matches('-').trim()),
+            Block::Paragraph(spans) => inline(spans),
+            Block::ListItem(spans) => format!("- {}", inline(spans)),
+            Block::Code(code) => format!("```\n{}\n```", code.trim_end()),
+        })
+        .collect();
+    // Keep list items together.
+    let mut out = String::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            let list = block.starts_with("- ") && blocks[i - 1].starts_with("- ");
+            out.push_str(if list { "\n" } else { "\n\n" });
+        }
+        out.push_str(block);
+    }
+    out
+}
+
+/// Renders cached descriptions as text wrapped to `width` columns, using ANSI
+/// escapes for emphasis and code if `ansi` is set.
+pub fn to_text(html: &str, width: usize, ansi: bool) -> String {
+    let style = |codes: &str, text: &str| {
+        if ansi {
+            format!("{}{}{}", codes, text, RESET)
+        } else {
+            text.to_string()
+        }
+    };
+    let mut out = String::new();
+    let mut prev_list_item = false;
+    for block in parse(html) {
+        let list_item = matches!(block, Block::ListItem(_));
+        // Separate blocks with a blank line, except within lists.
+        if !(out.is_empty() || list_item && prev_list_item) {
+            out.push('\n');
+        }
+        prev_list_item = list_item;
+        match block {
+            Block::Heading(spans) => {
+                let text: String = spans.iter().map(|s| s.text.as_str()).collect();
+                writeln!(out, "{}", style(BOLD, text.trim())).unwrap();
+            }
+            Block::Paragraph(spans) => out.push_str(&wrap(&spans, "", width, ansi)),
+            Block::ListItem(spans) => out.push_str(&wrap(&spans, "  - ", width, ansi)),
+            Block::Code(code) => {
+                for line in code.trim_end().lines() {
+                    writeln!(out, "    {}", style(CYAN, line)).unwrap();
+                }
+            }
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Wraps styled text to `width` columns, starting the first line with
+/// `prefix` and indenting the rest to match.
+fn wrap(spans: &[Span], prefix: &str, width: usize, ansi: bool) -> String {
+    // Split into words, each of which may be made up of several styled
+    // pieces, e.g. "`x`," is code followed by plain text.
+    let mut words: Vec<Vec<(&str, &Style)>> = vec![vec![]];
+    for span in spans {
+        let mut pieces = span.text.split(char::is_whitespace).peekable();
+        while let Some(piece) = pieces.next() {
+            if !piece.is_empty() {
+                words.last_mut().unwrap().push((piece, &span.style));
+            }
+            if pieces.peek().is_some() {
+                words.push(vec![]);
+            }
+        }
+    }
+
+    let mut out = String::from(prefix);
+    let indent = " ".repeat(prefix.len());
+    let mut column = prefix.len();
+    for word in words.iter().filter(|w| !w.is_empty()) {
+        let len: usize = word.iter().map(|(piece, _)| piece.chars().count()).sum();
+        if column > prefix.len() {
+            if column + 1 + len > width {
+                out.push('\n');
+                out.push_str(&indent);
+                column = indent.len();
+            } else {
+                out.push(' ');
+                column += 1;
+            }
+        }
+        for (piece, style) in word {
+            let codes: String = [
+                (style.em, BOLD),
+                (style.code, CYAN),
+                (style.link.is_some(), UNDERLINE),
+            ]
+            .iter()
+            .filter(|(on, _)| ansi && *on)
+            .map(|(_, code)| *code)
+            .collect();
+            if codes.is_empty() {
+                out.push_str(piece);
+            } else {
+                write!(out, "{}{}{}", codes, piece, RESET).unwrap();
+            }
+        }
+        column += len;
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{articles, title, to_markdown, to_text};
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Sonar Sweep ---</h2>
+<p>For example, suppose you had the following <a href="/2021/day/1/input">report</a>:</p>
+<pre><code>199
+<em>200</em>
+</code></pre>
+<p>In this example, there are <code><em>7</em></code> measurements that are larger than the previous measurement.</p>
+<ul>
+<li>one</li>
+<li>two</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>1532</code>.</p>
+</main>"#;
+
+    #[test]
+    fn test_articles() {
+        let found = articles(PAGE);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("<h2>"));
+        assert!(found[0].ends_with("</ul>\n"));
+    }
+
+    #[test]
+    fn test_title() {
+        assert_eq!(title(PAGE).as_deref(), Some("Sonar Sweep"));
+        assert_eq!(title("<p>Not a puzzle</p>"), None);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            to_markdown(articles(PAGE)[0]),
+            "\
+## Day 1: Sonar Sweep
+
+For example, suppose you had the following [report](/2021/day/1/input):
+
+```
+199
+200
+```
+
+In this example, there are **`7`** measurements that are larger than the previous measurement.
+
+- one
+- two"
+        );
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            to_text(articles(PAGE)[0], 40, false),
+            "\
+--- Day 1: Sonar Sweep ---
+
+For example, suppose you had the
+following report:
+
+    199
+    200
+
+In this example, there are 7
+measurements that are larger than the
+previous measurement.
+
+  - one
+  - two"
+        );
+        let ansi = to_text(articles(PAGE)[0], 40, true);
+        assert!(ansi.contains("are \x1b[1m\x1b[36m7\x1b[0m\n"), "{:?}", ansi);
+    }
+}
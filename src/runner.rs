@@ -0,0 +1,722 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use tracing::{debug, info_span, Span};
+
+use crate::{
+    answer::Answer,
+    answers::input_hash,
+    cache::Cache,
+    cli::Problem,
+    input::{self, Input, InputSource},
+    memory::{self, AllocStats},
+    output::{self, Format},
+    parse::ParseError,
+    solution::{DynSolution, Registry},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+    Panic,
+    Timeout,
+    /// The answer differs from the one given in the puzzle text.
+    Mismatch,
+    /// The part hasn't been solved yet.
+    Unsolved,
+}
+
+/// The outcome of running a single part.
+#[derive(Debug, Serialize)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub title: &'static str,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    /// The known answer for the input, if any.
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    #[serde(rename = "duration_ns", serialize_with = "serialize_nanos")]
+    pub duration: Duration,
+    pub input_hash: Option<String>,
+    pub input_source: Option<String>,
+    /// The allocations made by the part, if they're being counted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+/// The outcome of parsing a single input.
+#[derive(Debug, Serialize)]
+pub struct ParseResult {
+    pub day: u32,
+    pub input_source: String,
+    /// Whether parsing succeeded; when it didn't, every part of the input
+    /// carries the error.
+    pub status: Status,
+    #[serde(rename = "duration_ns", serialize_with = "serialize_nanos")]
+    pub duration: Duration,
+    /// The allocations made while parsing, if they're being counted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+/// The results of a run, along with the total time spent parsing and solving.
+#[derive(Debug)]
+pub struct Report {
+    pub results: Vec<PartResult>,
+    /// Every input that was parsed, in the order the results are in.
+    pub parses: Vec<ParseResult>,
+    pub total_duration: Duration,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Summary {
+    pub days: usize,
+    pub parts: usize,
+    pub passed: usize,
+    /// Parts that returned an error, panicked or got an example wrong.
+    pub failed: usize,
+    pub timed_out: usize,
+    pub unsolved: usize,
+    #[serde(rename = "total_duration_ns", serialize_with = "serialize_nanos")]
+    pub total_duration: Duration,
+}
+
+impl Report {
+    /// Counts how many parts passed, failed and timed out.
+    pub fn summary(&self) -> Summary {
+        let count = |status| self.results.iter().filter(|r| r.status == status).count();
+        let passed = count(Status::Ok);
+        let timed_out = count(Status::Timeout);
+        let unsolved = count(Status::Unsolved);
+        Summary {
+            days: self.results.iter().map(|r| r.day).dedup().count(),
+            parts: self.results.len(),
+            passed,
+            failed: self.results.len() - passed - timed_out - unsolved,
+            timed_out,
+            unsolved,
+            total_duration: self.total_duration,
+        }
+    }
+}
+
+impl Summary {
+    /// Whether every part that's been solved ran successfully.
+    pub fn succeeded(&self) -> bool {
+        self.failed == 0 && self.timed_out == 0
+    }
+}
+
+pub struct Options {
+    /// How long to wait for parsing or for a single part before giving up.
+    pub timeout: Option<Duration>,
+    /// How many days to run concurrently.
+    pub jobs: usize,
+}
+
+/// Runs the given problems, grouped by day so that each day's inputs are only
+/// loaded and parsed once, against each of the inputs `load_inputs` returns
+/// for the day in turn. Parsing and each part run on their own thread, so that
+/// errors, panics and timeouts are recorded in the results rather than
+/// stopping the run.
+pub fn run(
+    registry: &Registry,
+    problems: &[Problem],
+    load_inputs: impl Fn(u32) -> Result<Vec<Input>> + Sync,
+    options: &Options,
+) -> Report {
+    let days: Vec<(u32, Vec<Problem>)> = problems
+        .iter()
+        .group_by(|p| p.day)
+        .into_iter()
+        .map(|(day, problems)| (day, problems.copied().collect()))
+        .collect();
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(vec![]);
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| {
+                while let Some((day, problems)) = days.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let report = run_day(registry.get(*day), *day, problems, &load_inputs, options);
+                    reports.lock().unwrap().push(report);
+                }
+            });
+        }
+    });
+
+    let reports = reports.into_inner().unwrap();
+    let total_duration = reports.iter().map(|r| r.total_duration).sum();
+    let mut results = vec![];
+    let mut parses = vec![];
+    for report in reports {
+        results.extend(report.results);
+        parses.extend(report.parses);
+    }
+    // Each day's results are already in order.
+    results.sort_by_key(|r| r.day);
+    parses.sort_by_key(|p| p.day);
+    Report {
+        results,
+        parses,
+        total_duration,
+    }
+}
+
+/// Runs `problems` like [`run`] and formats the report. If any part failed or
+/// timed out, prints the report, so that it isn't lost, and fails. A single
+/// part run on a single input in plain format is shown without the summary,
+/// see [`output::single`].
+pub fn run_and_render(
+    registry: &Registry,
+    problems: &[Problem],
+    load_inputs: impl Fn(u32) -> Result<Vec<Input>> + Sync,
+    options: &Options,
+    format: Format,
+) -> Result<String> {
+    let report = run(registry, problems, load_inputs, options);
+    let summary = report.summary();
+    if summary.succeeded() {
+        return Ok(match (format, &report.results[..]) {
+            (Format::Plain, [_]) => output::single(&report),
+            _ => output::render(format, &report),
+        });
+    }
+    println!("{}", output::render(format, &report));
+    Err(anyhow!(
+        "{} part(s) failed, {} timed out",
+        summary.failed,
+        summary.timed_out
+    ))
+}
+
+/// Solves a single problem with the puzzle input, running it like [`run`] so
+/// that a panic or timeout is returned as an error.
+pub fn solve_puzzle(
+    registry: &Registry,
+    cache: &Cache,
+    problem: Problem,
+    options: &Options,
+) -> Result<Answer> {
+    let load_inputs = |day| {
+        let input = input::load(Some(cache), &InputSource::Puzzle, None, day)
+            .context("failed to load input")?;
+        Ok(vec![input])
+    };
+    let report = run(registry, &[problem], load_inputs, options);
+    match report.results.into_iter().next() {
+        Some(PartResult {
+            answer: Some(answer),
+            ..
+        }) => Ok(answer),
+        Some(PartResult {
+            error: Some(err), ..
+        }) => Err(anyhow!(err)),
+        _ => Err(anyhow!(
+            "day {} part {} didn't run",
+            problem.day,
+            problem.part
+        )),
+    }
+}
+
+fn run_day(
+    solution: Option<&'static dyn DynSolution>,
+    day: u32,
+    problems: &[Problem],
+    load_inputs: &impl Fn(u32) -> Result<Vec<Input>>,
+    options: &Options,
+) -> Report {
+    let title = solution.map_or("", |s| s.title());
+    let loaded = match solution {
+        None => Err(format!("no solution for day {}", day)),
+        Some(solution) => load_inputs(day)
+            .map(|inputs| (solution, inputs))
+            .map_err(|err| format!("{:#}", err)),
+    };
+    let (solution, inputs) = match loaded {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            let results = problems
+                .iter()
+                .map(|problem| PartResult {
+                    day,
+                    part: problem.part,
+                    title,
+                    status: Status::Error,
+                    answer: None,
+                    expected: None,
+                    error: Some(err.clone()),
+                    duration: Duration::ZERO,
+                    input_hash: None,
+                    input_source: None,
+                    alloc: None,
+                })
+                .collect();
+            return Report {
+                results,
+                parses: vec![],
+                total_duration: Duration::ZERO,
+            };
+        }
+    };
+
+    let mut results = vec![];
+    let mut parses = vec![];
+    let mut total_duration = Duration::ZERO;
+    for input in inputs {
+        let report = run_input(solution, problems, input, options);
+        results.extend(report.results);
+        parses.extend(report.parses);
+        total_duration += report.total_duration;
+    }
+    Report {
+        results,
+        parses,
+        total_duration,
+    }
+}
+
+fn run_input(
+    solution: &'static dyn DynSolution,
+    problems: &[Problem],
+    input: Input,
+    options: &Options,
+) -> Report {
+    let _span = info_span!("input", day = solution.day(), source = input.source).entered();
+    let hash = input_hash(&input.text);
+    let text = input.text;
+    let (parsed, mut total_duration, alloc) =
+        isolated(options.timeout, move || solution.parse(&text));
+    let parsed = parsed.map(Arc::new);
+    let parses = vec![ParseResult {
+        day: solution.day(),
+        input_source: input.source.clone(),
+        status: parsed
+            .as_ref()
+            .err()
+            .map_or(Status::Ok, |(status, _)| *status),
+        duration: total_duration,
+        alloc,
+    }];
+
+    let mut results = vec![];
+    for problem in problems {
+        let (outcome, duration, alloc) = match &parsed {
+            Ok(parsed) => {
+                let (parsed, part) = (Arc::clone(parsed), problem.part);
+                isolated(options.timeout, move || solution.solve(&parsed, part))
+            }
+            Err(err) => (Err(err.clone()), Duration::ZERO, None),
+        };
+        total_duration += duration;
+
+        debug!(
+            part = problem.part,
+            ?duration,
+            ok = outcome.is_ok(),
+            "part finished"
+        );
+        let expected = input.expected.get(&problem.part).cloned();
+        let (status, answer, error) = match outcome {
+            Ok(Answer::Unsolved) => (Status::Unsolved, Some(Answer::Unsolved), None),
+            Ok(answer) if expected.as_ref().is_some_and(|e| !answer.matches(e)) => {
+                (Status::Mismatch, Some(answer), None)
+            }
+            Ok(answer) => (Status::Ok, Some(answer), None),
+            Err((status, err)) => (status, None, Some(err)),
+        };
+        results.push(PartResult {
+            day: problem.day,
+            part: problem.part,
+            title: solution.title(),
+            status,
+            answer,
+            expected,
+            error,
+            duration,
+            input_hash: Some(hash.clone()),
+            input_source: Some(input.source.clone()),
+            alloc,
+        });
+    }
+    Report {
+        results,
+        parses,
+        total_duration,
+    }
+}
+
+thread_local! {
+    /// Whether the current thread was started by `isolated`.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    /// Where the last panic on this thread happened, if it was isolated.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// Stops panics in isolated threads from being printed, since they're reported
+/// in the results instead, but remembers where they happened.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.with(Cell::get) {
+                let location = info.location().map(|l| l.to_string());
+                PANIC_LOCATION.with(|p| *p.borrow_mut() = location);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f` on its own thread, returning how long it took and the allocations
+/// it made, and turning panics and running for longer than `timeout` into
+/// errors. A thread that times out can't be stopped, so it's left running in
+/// the background.
+pub(crate) fn isolated<T: Send + 'static>(
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> (Result<T, (Status, String)>, Duration, Option<AllocStats>) {
+    install_panic_hook();
+    let (tx, rx) = mpsc::channel();
+    // Spans don't follow work onto other threads by themselves.
+    let span = Span::current();
+    thread::spawn(move || {
+        let _span = span.entered();
+        ISOLATED.with(|i| i.set(true));
+        let ((res, duration), alloc) = memory::measure(|| {
+            let start = Instant::now();
+            let res = panic::catch_unwind(AssertUnwindSafe(f));
+            (res, start.elapsed())
+        });
+        let outcome = match res {
+            Ok(Ok(val)) => Ok(val),
+            Ok(Err(err)) => Err((Status::Error, error_message(&err))),
+            Err(payload) => {
+                let location = PANIC_LOCATION.with(|p| p.borrow_mut().take());
+                Err((Status::Panic, panic_message(payload, location)))
+            }
+        };
+        // The receiver is gone if we timed out, in which case there's no one
+        // left to report to.
+        let _ = tx.send((outcome, duration, alloc));
+    });
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout).map_err(|_| timeout),
+        None => rx.recv().map_err(|_| Duration::ZERO),
+    };
+    received.unwrap_or_else(|timeout| {
+        (
+            Err((Status::Timeout, format!("timed out after {:?}", timeout))),
+            timeout,
+            None,
+        )
+    })
+}
+
+/// Formats an error on one line, unless it's a parse error, which is followed
+/// by the line of input it points at.
+fn error_message(err: &anyhow::Error) -> String {
+    match err.downcast_ref::<ParseError>() {
+        Some(err) => format!("{}\n{}", err, err.snippet()),
+        None => format!("{:#}", err),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>, location: Option<String>) -> String {
+    let message = if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    };
+    match location {
+        Some(location) => format!("panicked at {}: {}", location, message),
+        None => format!("panicked: {}", message),
+    }
+}
+
+fn serialize_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, env, fs, process, thread, time::Duration};
+
+    use anyhow::{anyhow, Result};
+
+    use super::{run, run_and_render, solve_puzzle, Options, Status};
+    use crate::{
+        answer::Answer,
+        answers::input_hash,
+        cache::Cache,
+        cli::Problem,
+        input::Input,
+        memory,
+        output::Format,
+        solution::{Registry, Solution},
+    };
+
+    fn input(text: &str) -> Result<Vec<Input>> {
+        Ok(vec![Input {
+            text: text.to_string(),
+            source: "test".to_string(),
+            expected: BTreeMap::new(),
+        }])
+    }
+
+    /// Part 1 panics and part 2 never finishes.
+    struct Faulty;
+
+    impl Solution for Faulty {
+        const YEAR: i32 = 2021;
+        const DAY: u32 = 1;
+        const TITLE: &'static str = "Faulty";
+        type Input = ();
+
+        fn parse(_: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> Result<Answer> {
+            panic!("oh no");
+        }
+
+        fn part2(_: &()) -> Result<Answer> {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+
+    const OPTIONS: Options = Options {
+        timeout: None,
+        jobs: 1,
+    };
+
+    #[test]
+    fn test_run_records_failures() {
+        let problems = [
+            Problem { day: 1, part: 1 },
+            Problem { day: 1, part: 2 },
+            Problem { day: 2, part: 1 },
+        ];
+        let report = run(
+            &Registry::new(2021),
+            &problems,
+            |day| match day {
+                1 => input("1\n3\n2\n4\n"),
+                _ => Err(anyhow!("no input")),
+            },
+            &OPTIONS,
+        );
+
+        let r = &report.results;
+        assert_eq!(r.len(), 3);
+        assert_eq!(r[0].status, Status::Ok);
+        assert_eq!(r[0].answer, Some(Answer::Unsigned(2)));
+        assert_eq!(r[0].title, "Sonar Sweep");
+        assert_eq!(r[0].input_hash, Some(input_hash("1\n3\n2\n4\n")));
+        assert_eq!(r[0].input_source.as_deref(), Some("test"));
+        assert_eq!(r[1].answer, Some(Answer::Unsigned(1)));
+        assert_eq!(r[2].status, Status::Error);
+        assert_eq!(r[2].error.as_deref(), Some("no input"));
+        assert_eq!(r[2].input_hash, None);
+        assert_eq!(r[0].alloc.is_some(), memory::ENABLED);
+        assert_eq!(report.parses.len(), 1);
+        assert_eq!(report.parses[0].input_source, "test");
+        assert_eq!(report.parses[0].status, Status::Ok);
+        assert_eq!(report.parses[0].alloc.is_some(), memory::ENABLED);
+
+        let summary = report.summary();
+        assert_eq!((summary.days, summary.parts), (2, 3));
+        assert_eq!((summary.passed, summary.failed), (2, 1));
+    }
+
+    #[test]
+    fn test_run_isolates_panics_and_timeouts() {
+        let registry = Registry::from_solutions(vec![&Faulty]);
+        let problems = [Problem { day: 1, part: 1 }, Problem { day: 1, part: 2 }];
+        let options = Options {
+            timeout: Some(Duration::from_millis(50)),
+            jobs: 1,
+        };
+        let report = run(&registry, &problems, |_| input(""), &options);
+
+        let r = &report.results;
+        assert_eq!(r[0].status, Status::Panic);
+        let error = r[0].error.as_deref().unwrap();
+        assert!(error.starts_with("panicked at src/runner.rs:"), "{}", error);
+        assert!(error.ends_with(": oh no"), "{}", error);
+        assert_eq!(r[1].status, Status::Timeout);
+        assert_eq!(r[1].error.as_deref(), Some("timed out after 50ms"));
+
+        let summary = report.summary();
+        assert_eq!(
+            (summary.passed, summary.failed, summary.timed_out),
+            (0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_run_and_render_single_part() {
+        let registry = Registry::new(2021);
+        let problems = [Problem { day: 1, part: 1 }];
+        let out = run_and_render(
+            &registry,
+            &problems,
+            |_| input("1\n3\n"),
+            &OPTIONS,
+            Format::Plain,
+        );
+        assert_eq!(out.unwrap(), "Day #1: Sonar Sweep (test)\n  Part 1: 1");
+
+        let puzzle = |_| {
+            let mut inputs = input("1\n3\n")?;
+            inputs[0].source = "puzzle input".to_string();
+            Ok(inputs)
+        };
+        let out = run_and_render(&registry, &problems, puzzle, &OPTIONS, Format::Plain);
+        assert_eq!(out.unwrap(), "1");
+
+        // A panic is caught and reported rather than aborting.
+        let registry = Registry::from_solutions(vec![&Faulty]);
+        let out = run_and_render(&registry, &problems, |_| input(""), &OPTIONS, Format::Plain);
+        assert_eq!(
+            out.unwrap_err().to_string(),
+            "1 part(s) failed, 0 timed out"
+        );
+    }
+
+    #[test]
+    fn test_solve_puzzle() {
+        let root = env::temp_dir().join(format!("aoc-runner-{}", process::id()));
+        let cache = Cache::new(&root, 2021, "abc123");
+        cache.write("day01.txt", "1\n3\n").unwrap();
+        let options = Options {
+            timeout: Some(Duration::from_millis(50)),
+            jobs: 1,
+        };
+
+        let registry = Registry::new(2021);
+        let problem = Problem { day: 1, part: 1 };
+        let answer = solve_puzzle(&registry, &cache, problem, &options).unwrap();
+        assert_eq!(answer.to_string(), "1");
+
+        // Panics and timeouts are errors rather than aborting.
+        let registry = Registry::from_solutions(vec![&Faulty]);
+        let err = solve_puzzle(&registry, &cache, problem, &options).unwrap_err();
+        assert!(err.to_string().contains("oh no"), "{}", err);
+        let problem = Problem { day: 1, part: 2 };
+        let err = solve_puzzle(&registry, &cache, problem, &options).unwrap_err();
+        assert!(err.to_string().starts_with("timed out"), "{}", err);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_run_unsolved() {
+        let problems = [Problem { day: 17, part: 1 }, Problem { day: 17, part: 2 }];
+        let report = run(
+            &Registry::new(2021),
+            &problems,
+            |_| input("target area: x=20..30, y=-10..-5"),
+            &OPTIONS,
+        );
+        assert_eq!(report.results[1].status, Status::Unsolved);
+        let summary = report.summary();
+        assert_eq!(
+            (summary.passed, summary.failed, summary.unsolved),
+            (1, 0, 1)
+        );
+        assert!(summary.succeeded());
+    }
+
+    #[test]
+    fn test_run_shows_where_parsing_failed() {
+        let problems = [Problem { day: 1, part: 1 }];
+        let report = run(
+            &Registry::new(2021),
+            &problems,
+            |_| input("1\n3x\n"),
+            &OPTIONS,
+        );
+        assert_eq!(report.parses[0].status, Status::Error);
+        assert_eq!(
+            report.results[0].error.as_deref(),
+            Some(
+                "day 1 input, line 2, column 1: expected a number, found \"3x\"\n  |\n2 | 3x\n  | ^^"
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_in_parallel() {
+        let problems: Vec<_> = (1..=8).map(|day| Problem { day, part: 1 }).collect();
+        let options = Options {
+            timeout: None,
+            jobs: 4,
+        };
+        let report = run(
+            &Registry::new(2021),
+            &problems,
+            |day| Err(anyhow!("no input for {}", day)),
+            &options,
+        );
+        let days: Vec<_> = report.results.iter().map(|r| r.day).collect();
+        assert_eq!(days, (1..=8).collect::<Vec<_>>());
+        assert_eq!(report.results[4].error.as_deref(), Some("no input for 5"));
+    }
+
+    #[test]
+    fn test_run_examples_first() {
+        let problems = [Problem { day: 1, part: 1 }, Problem { day: 1, part: 2 }];
+        let inputs = |_| {
+            let example = Input {
+                text: "1\n3\n2\n4\n".to_string(),
+                source: "example 1".to_string(),
+                expected: BTreeMap::from([(1, "2".to_string()), (2, "2".to_string())]),
+            };
+            Ok(vec![example, input("5\n6\n")?.remove(0)])
+        };
+        let report = run(&Registry::new(2021), &problems, inputs, &OPTIONS);
+
+        let r: Vec<_> = report
+            .results
+            .iter()
+            .map(|r| (r.input_source.as_deref().unwrap(), r.part, r.status))
+            .collect();
+        assert_eq!(
+            r,
+            vec![
+                ("example 1", 1, Status::Ok),
+                ("example 1", 2, Status::Mismatch),
+                ("test", 1, Status::Ok),
+                ("test", 2, Status::Ok),
+            ]
+        );
+        assert_eq!(report.results[1].answer, Some(Answer::Unsigned(1)));
+        assert_eq!(report.results[1].expected.as_deref(), Some("2"));
+        assert_eq!(report.summary().failed, 1);
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{cache::Cache, examples, input, puzzle};
+
+/// What's known about a day before it's been solved, used to generate its
+/// module.
+pub struct Scaffold {
+    pub year: i32,
+    pub day: u32,
+    pub title: Option<String>,
+    /// The first example from the puzzle text, if it's been fetched.
+    pub example: Option<String>,
+    /// The example's answers by part, where they're known.
+    pub answers: BTreeMap<u32, String>,
+}
+
+impl Scaffold {
+    /// What the cached puzzle says about the day, fetching the puzzle first if
+    /// it isn't cached.
+    pub fn from_puzzle(cache: &Cache, day: u32) -> Result<Scaffold> {
+        let html = match puzzle::load(cache, day) {
+            Ok(html) => html,
+            Err(_) => {
+                puzzle::fetch(cache, day)?;
+                puzzle::load(cache, day)?
+            }
+        };
+        Ok(Scaffold {
+            year: cache.year(),
+            day,
+            title: puzzle::title(&html),
+            example: cache.read(&input::example_name(day, 1))?,
+            answers: examples::load_answers(cache, day)?
+                .into_iter()
+                .filter(|(_, a)| a.example == Some(1))
+                .map(|(part, a)| (part, a.answer))
+                .collect(),
+        })
+    }
+
+    /// Renders the source of the day's module: a `Solution` impl, stubs for
+    /// parsing and both parts, and tests against the example.
+    pub fn render(&self) -> String {
+        let title = self.title.as_deref().unwrap_or("TODO");
+        let mut out = format!(
+            r#"//! Day {day}: {title}, <https://adventofcode.com/{year}/day/{day}>.
+
+use anyhow::Result;
+
+use crate::{{answer::Answer, parse::ParseError, solution::Solution}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const YEAR: i32 = {year};
+    const DAY: u32 = {day};
+    const TITLE: &'static str = "{title}";
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>> {{
+        Ok(parse_input(input)?)
+    }}
+
+    fn part1(input: &Vec<String>) -> Result<Answer> {{
+        part1(input)
+    }}
+
+    fn part2(input: &Vec<String>) -> Result<Answer> {{
+        part2(input)
+    }}
+}}
+
+pub fn part1(_input: &[String]) -> Result<Answer> {{
+    Ok(Answer::Unsolved)
+}}
+
+pub fn part2(_input: &[String]) -> Result<Answer> {{
+    Ok(Answer::Unsolved)
+}}
+
+/// Parses the puzzle input, one line at a time.
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {{
+    Ok(input.trim().lines().map(String::from).collect())
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::{{parse_input, part1, part2}};
+
+    const TEST_INPUT: &str = "
+{example}";
+"#,
+            year = self.year,
+            day = self.day,
+            title = escape(title),
+            example = escape(self.example.as_deref().unwrap_or("TODO\n")),
+        );
+        for part in [1, 2] {
+            out.push('\n');
+            out.push_str("    #[test]\n");
+            if !self.answers.contains_key(&part) {
+                out.push_str("    #[ignore = \"no known answer yet\"]\n");
+            }
+            let answer = self
+                .answers
+                .get(&part)
+                .map_or("TODO".to_string(), |a| escape(a));
+            writeln!(
+                out,
+                "    fn test_part{part}() {{
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part{part}(&input).unwrap(), \"{answer}\");
+    }}",
+                part = part,
+                answer = answer,
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Writes the day's module to `src/yYYYY/dayNN.rs` under `src_dir`,
+    /// refusing to overwrite an existing one. `build.rs` registers it on the
+    /// next build.
+    pub fn create(&self, src_dir: &Path) -> Result<PathBuf> {
+        let dir = src_dir.join(format!("y{}", self.year));
+        let path = dir.join(format!("day{:02}.rs", self.day));
+        if path.exists() {
+            return Err(anyhow!(
+                "day {} of {} already exists at {}",
+                self.day,
+                self.year,
+                path.display()
+            ));
+        }
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        fs::write(&path, self.render())
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Escapes text for use inside a Rust string literal.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, env, fs, process};
+
+    use super::Scaffold;
+
+    fn scaffold() -> Scaffold {
+        Scaffold {
+            year: 2021,
+            day: 18,
+            title: Some("Snailfish".to_string()),
+            example: Some("[1,2]\n[[3,4],\"5\"]\n".to_string()),
+            answers: BTreeMap::from([(1, "4140".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let source = scaffold().render();
+        assert!(
+            source.starts_with("//! Day 18: Snailfish, <https://adventofcode.com/2021/day/18>.\n")
+        );
+        assert!(source.contains("pub struct Day18;"));
+        assert!(source.contains("    const YEAR: i32 = 2021;\n    const DAY: u32 = 18;\n"));
+        assert!(source.contains("const TITLE: &'static str = \"Snailfish\";"));
+        assert!(source.contains("const TEST_INPUT: &str = \"\n[1,2]\n[[3,4],\\\"5\\\"]\n\";"));
+        assert!(source.contains(
+            "    #[test]\n    fn test_part1() {\n        let input = parse_input(TEST_INPUT).unwrap();\n        assert_eq!(part1(&input).unwrap(), \"4140\");"
+        ));
+        assert!(source.contains("    #[ignore = \"no known answer yet\"]\n    fn test_part2()"));
+    }
+
+    #[test]
+    fn test_create_refuses_to_overwrite() {
+        let src_dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let path = scaffold().create(&src_dir).unwrap();
+        assert_eq!(path, src_dir.join("y2021").join("day18.rs"));
+        assert!(scaffold().create(&src_dir).is_err());
+        fs::remove_dir_all(src_dir).unwrap();
+    }
+}
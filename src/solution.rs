@@ -0,0 +1,254 @@
+use std::{any::Any, error::Error, fmt};
+
+use anyhow::{anyhow, Result};
+use tracing::info_span;
+
+use crate::answer::Answer;
+
+/// A solution to a single day's problem.
+///
+/// Every `src/yYYYY/dayNN.rs` file defines a unit struct named `DayNN`
+/// implementing this trait, and `build.rs` registers it automatically.
+pub trait Solution {
+    /// The year of the event the problem is from.
+    const YEAR: i32;
+    /// The day of the month the problem was released on.
+    const DAY: u32;
+    /// The title of the problem, e.g. "Sonar Sweep".
+    const TITLE: &'static str;
+
+    /// The result of parsing the raw puzzle input, shared by both parts.
+    type Input: Send + Sync + 'static;
+
+    /// Optional information about the solution, shown when listing days.
+    fn metadata() -> Metadata {
+        Metadata::default()
+    }
+
+    /// Turns the raw puzzle input into the input for both parts.
+    fn parse(input: &str) -> Result<Self::Input>;
+    /// Solves part 1.
+    fn part1(input: &Self::Input) -> Result<Answer>;
+    /// Solves part 2, or returns [`Answer::Unsolved`] if it isn't solved yet.
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Metadata {
+    /// A short note on the approach taken.
+    pub notes: Option<&'static str>,
+}
+
+/// Why a part couldn't be solved for an input that parsed fine, but that the
+/// puzzle has no answer for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// There are fewer of something than the puzzle needs, e.g. fewer than
+    /// three basins to multiply the sizes of, or an operator packet without
+    /// enough subpackets to apply its operation to.
+    TooFew {
+        what: &'static str,
+        needed: usize,
+        found: usize,
+    },
+    /// An operator packet's type ID doesn't name an operation.
+    UnknownOperator(u64),
+    /// The numbers ran out before any board won.
+    NoWinner,
+    /// The numbers ran out with some boards still to win, so there's no last
+    /// board to win.
+    NoLastWinner { left: usize },
+    /// A display's signal patterns don't wire up the digits 0 to 9, so its
+    /// outputs can't be read. Entries are numbered from 1.
+    Unwired { entry: usize },
+    /// A dot is further past a fold line than the line is from the edge of the
+    /// paper, so folding would take it off the paper.
+    OffPaper { x: u32, y: u32 },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::TooFew {
+                what,
+                needed,
+                found,
+            } => write!(f, "needed at least {} {}, found {}", needed, what, found),
+            SolveError::UnknownOperator(type_id) => {
+                write!(f, "unknown operator packet type ID {}", type_id)
+            }
+            SolveError::NoWinner => write!(f, "no board won"),
+            SolveError::NoLastWinner { left } => write!(f, "{} board(s) never won", left),
+            SolveError::Unwired { entry } => {
+                write!(f, "entry {} doesn't wire up the digits 0 to 9", entry)
+            }
+            SolveError::OffPaper { x, y } => {
+                write!(f, "folding the dot at {},{} takes it off the paper", x, y)
+            }
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Parsed input whose concrete type is only known to the solution that produced
+/// it.
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+/// Object-safe counterpart of `Solution`, so that all the days can be stored
+/// in a single registry.
+pub trait DynSolution: Sync {
+    /// See [`Solution::YEAR`].
+    fn year(&self) -> i32;
+    /// See [`Solution::DAY`].
+    fn day(&self) -> u32;
+    /// See [`Solution::TITLE`].
+    fn title(&self) -> &'static str;
+    /// See [`Solution::metadata`].
+    fn metadata(&self) -> Metadata;
+    /// Parses the input, type-erasing the result.
+    fn parse(&self, input: &str) -> Result<Parsed>;
+    /// Solves part 1, failing if `input` came from a different day.
+    fn part1(&self, input: &Parsed) -> Result<Answer>;
+    /// Solves part 2, failing if `input` came from a different day.
+    fn part2(&self, input: &Parsed) -> Result<Answer>;
+
+    /// Solves part 1 or 2.
+    fn solve(&self, input: &Parsed, part: u32) -> Result<Answer> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(anyhow!("invalid part {}", part)),
+        }
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn year(&self) -> i32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn metadata(&self) -> Metadata {
+        S::metadata()
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed> {
+        let _span = info_span!("parse", day = S::DAY).entered();
+        Ok(Parsed(Box::new(S::parse(input)?)))
+    }
+
+    fn part1(&self, input: &Parsed) -> Result<Answer> {
+        let _span = info_span!("part", day = S::DAY, part = 1).entered();
+        S::part1(downcast::<S>(input)?)
+    }
+
+    fn part2(&self, input: &Parsed) -> Result<Answer> {
+        let _span = info_span!("part", day = S::DAY, part = 2).entered();
+        S::part2(downcast::<S>(input)?)
+    }
+}
+
+fn downcast<S: Solution>(input: &Parsed) -> Result<&S::Input> {
+    input
+        .0
+        .downcast_ref()
+        .ok_or_else(|| anyhow!("input was not parsed by day {}", S::DAY))
+}
+
+/// The registered solutions for a single year, ordered by day.
+pub struct Registry {
+    solutions: Vec<&'static dyn DynSolution>,
+}
+
+impl Registry {
+    /// The registry of every solution for `year`.
+    pub fn new(year: i32) -> Registry {
+        Registry::from_solutions(
+            crate::registered_solutions()
+                .into_iter()
+                .filter(|s| s.year() == year)
+                .collect(),
+        )
+    }
+
+    /// A registry of the given solutions, which needn't be in order.
+    pub fn from_solutions(mut solutions: Vec<&'static dyn DynSolution>) -> Registry {
+        solutions.sort_by_key(|s| s.day());
+        Registry { solutions }
+    }
+
+    /// The solution for `day`, if there is one.
+    pub fn get(&self, day: u32) -> Option<&'static dyn DynSolution> {
+        self.solutions.iter().copied().find(|s| s.day() == day)
+    }
+
+    /// Like `get`, but fails if there's no solution for the day.
+    pub fn lookup(&self, day: u32) -> Result<&'static dyn DynSolution> {
+        self.get(day)
+            .ok_or_else(|| anyhow!("no solution for day {}", day))
+    }
+
+    /// Parses `input` and solves one part of the day's problem with it.
+    pub fn solve(&self, day: u32, part: u32, input: &str) -> Result<Answer> {
+        let solution = self.lookup(day)?;
+        let parsed = solution.parse(input)?;
+        solution.solve(&parsed, part)
+    }
+
+    /// Every solution, in order of day.
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn DynSolution> + '_ {
+        self.solutions.iter().copied()
+    }
+}
+
+/// The years that have any solutions, in order.
+pub fn years() -> Vec<i32> {
+    let mut years: Vec<i32> = crate::registered_solutions()
+        .iter()
+        .map(|s| s.year())
+        .collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{years, Registry};
+
+    #[test]
+    fn test_registry_days_unique() {
+        for year in years() {
+            let registry = Registry::new(year);
+            assert!(registry.iter().count() > 0);
+            assert!(registry.iter().all(|s| s.year() == year));
+            assert!(registry.iter().map(|s| s.day()).all_unique());
+        }
+    }
+
+    #[test]
+    fn test_registry_get() {
+        let registry = Registry::new(2021);
+        let day = registry.get(1).unwrap();
+        assert_eq!(day.day(), 1);
+        assert_eq!(day.title(), "Sonar Sweep");
+        assert!(registry.get(26).is_none());
+    }
+
+    #[test]
+    fn test_parse_then_solve() {
+        let day = Registry::new(2021).get(1).unwrap();
+        let parsed = day.parse("1\n3\n2\n4\n").unwrap();
+        assert_eq!(day.part1(&parsed).unwrap(), "2");
+    }
+}
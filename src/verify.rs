@@ -0,0 +1,327 @@
+use std::{fmt::Write, sync::Arc, time::Duration};
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    answers::{input_hash, AnswerStore},
+    runner::isolated,
+    solution::Registry,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    /// The solution's answer differs from the recorded one.
+    Fail {
+        expected: String,
+    },
+    /// Loading the input, parsing it or solving the part failed, panicked or
+    /// timed out.
+    Error(String),
+    /// No answer has been recorded for this input.
+    Unrecorded,
+    /// The part hasn't been solved yet, so there's nothing to check.
+    Unsolved,
+}
+
+#[derive(Debug)]
+pub struct CaseResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<Answer>,
+    pub status: Status,
+}
+
+/// Runs both parts of each of `days` and compares the answers against those
+/// recorded in `store`. Parsing and each part run in isolation like they do
+/// for `run`, so that a panic or running for longer than `timeout` is an error
+/// for that part rather than stopping the rest.
+pub fn verify(
+    registry: &Registry,
+    days: &[u32],
+    store: &AnswerStore,
+    load_input: impl Fn(u32) -> Result<String>,
+    timeout: Option<Duration>,
+) -> Vec<CaseResult> {
+    let mut results = vec![];
+    for &day in days {
+        let parsed = registry
+            .get(day)
+            .ok_or_else(|| format!("no solution for day {}", day))
+            .and_then(|solution| {
+                let input = load_input(day).map_err(|err| format!("{:#}", err))?;
+                let hash = input_hash(&input);
+                let (parsed, _, _) = isolated(timeout, move || solution.parse(&input));
+                let parsed = parsed.map_err(|(_, err)| err)?;
+                Ok((solution, hash, Arc::new(parsed)))
+            });
+        for part in [1, 2] {
+            let (solution, hash, parsed) = match &parsed {
+                Ok(p) => p,
+                Err(err) => {
+                    results.push(CaseResult {
+                        day,
+                        part,
+                        answer: None,
+                        status: Status::Error(err.clone()),
+                    });
+                    continue;
+                }
+            };
+            let (solution, parsed) = (*solution, Arc::clone(parsed));
+            let (outcome, _, _) = isolated(timeout, move || solution.solve(&parsed, part));
+            let (answer, status) = match outcome {
+                Ok(Answer::Unsolved) => (Some(Answer::Unsolved), Status::Unsolved),
+                Ok(answer) => {
+                    let status = match store.get(day, part, hash) {
+                        Some(expected) if answer.matches(expected) => Status::Pass,
+                        Some(expected) => Status::Fail {
+                            expected: expected.to_string(),
+                        },
+                        None => Status::Unrecorded,
+                    };
+                    (Some(answer), status)
+                }
+                Err((_, err)) => (None, Status::Error(err)),
+            };
+            results.push(CaseResult {
+                day,
+                part,
+                answer,
+                status,
+            });
+        }
+    }
+    results
+}
+
+/// Counts of results by status: passed, failed, errored, unrecorded and
+/// unsolved.
+pub fn counts(results: &[CaseResult]) -> (usize, usize, usize, usize, usize) {
+    let count = |f: fn(&Status) -> bool| results.iter().filter(|r| f(&r.status)).count();
+    (
+        count(|s| matches!(s, Status::Pass)),
+        count(|s| matches!(s, Status::Fail { .. })),
+        count(|s| matches!(s, Status::Error(_))),
+        count(|s| matches!(s, Status::Unrecorded)),
+        count(|s| matches!(s, Status::Unsolved)),
+    )
+}
+
+/// Formats one line per result followed by a summary line.
+pub fn report(results: &[CaseResult]) -> String {
+    let mut out = String::new();
+    for r in results {
+        let status = match &r.status {
+            Status::Pass => "ok".to_string(),
+            Status::Fail { expected } => {
+                format!("MISMATCH: got {}, expected {}", answer_text(r), expected)
+            }
+            Status::Error(err) => format!("ERROR: {}", err),
+            Status::Unrecorded => "no recorded answer".to_string(),
+            Status::Unsolved => "unsolved".to_string(),
+        };
+        writeln!(out, "Day {:>2} part {}: {}", r.day, r.part, status).unwrap();
+    }
+    let (passed, failed, errors, unrecorded, unsolved) = counts(results);
+    write!(
+        out,
+        "{} passed, {} failed, {} errors, {} unrecorded, {} unsolved",
+        passed, failed, errors, unrecorded, unsolved
+    )
+    .unwrap();
+    out
+}
+
+/// Formats the results as a JUnit XML report, with unrecorded answers and
+/// unsolved parts marked as skipped.
+pub fn to_junit(results: &[CaseResult]) -> String {
+    let (_, failed, errors, unrecorded, unsolved) = counts(results);
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        out,
+        "<testsuite name=\"aoc\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\">",
+        results.len(),
+        failed,
+        errors,
+        unrecorded + unsolved
+    )
+    .unwrap();
+    for r in results {
+        write!(
+            out,
+            "  <testcase classname=\"day{:02}\" name=\"part{}\"",
+            r.day, r.part
+        )
+        .unwrap();
+        match &r.status {
+            Status::Pass => out.push_str("/>\n"),
+            Status::Fail { expected } => {
+                let message = format!("got {}, expected {}", answer_text(r), expected);
+                writeln!(
+                    out,
+                    ">\n    <failure message=\"{}\"/>\n  </testcase>",
+                    xml_escape(&message)
+                )
+                .unwrap();
+            }
+            Status::Error(err) => writeln!(
+                out,
+                ">\n    <error message=\"{}\"/>\n  </testcase>",
+                xml_escape(err)
+            )
+            .unwrap(),
+            Status::Unrecorded | Status::Unsolved => {
+                out.push_str(">\n    <skipped/>\n  </testcase>\n")
+            }
+        }
+    }
+    out.push_str("</testsuite>\n");
+    out
+}
+
+fn answer_text(r: &CaseResult) -> String {
+    r.answer.as_ref().map(Answer::to_string).unwrap_or_default()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process, thread, time::Duration};
+
+    use anyhow::{anyhow, Result};
+
+    use super::{counts, to_junit, verify, CaseResult, Status};
+    use crate::{
+        answer::Answer,
+        answers::{input_hash, AnswerStore},
+        solution::{Registry, Solution},
+    };
+
+    #[test]
+    fn test_verify() {
+        let input = "1\n3\n2\n4\n";
+        let path = env::temp_dir().join(format!("aoc-verify-{}.json", process::id()));
+        let mut store = AnswerStore::load(&path).unwrap();
+        store.record(1, 1, &input_hash(input), "2");
+        store.record(1, 2, &input_hash(input), "5");
+
+        let registry = Registry::new(2021);
+        let results = verify(
+            &registry,
+            &[1, 2],
+            &store,
+            |day| match day {
+                1 => Ok(input.to_string()),
+                _ => Err(anyhow!("no input")),
+            },
+            None,
+        );
+        let statuses: Vec<_> = results.iter().map(|r| &r.status).collect();
+        assert_eq!(statuses[0], &Status::Pass);
+        assert_eq!(
+            statuses[1],
+            &Status::Fail {
+                expected: "5".to_string()
+            }
+        );
+        assert_eq!(statuses[2], &Status::Error("no input".to_string()));
+        assert_eq!(counts(&results), (1, 1, 2, 0, 0));
+    }
+
+    struct Faulty;
+
+    impl Solution for Faulty {
+        const YEAR: i32 = 2021;
+        const DAY: u32 = 1;
+        const TITLE: &'static str = "Faulty";
+        type Input = ();
+
+        fn parse(_: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> Result<Answer> {
+            panic!("oh no");
+        }
+
+        fn part2(_: &()) -> Result<Answer> {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_isolates_panics_and_timeouts() {
+        let path = env::temp_dir().join(format!("aoc-verify-faulty-{}.json", process::id()));
+        let store = AnswerStore::load(&path).unwrap();
+        let registry = Registry::from_solutions(vec![&Faulty]);
+        let timeout = Some(Duration::from_millis(50));
+        let results = verify(&registry, &[1], &store, |_| Ok(String::new()), timeout);
+        match &results[0].status {
+            Status::Error(err) => assert!(err.contains("oh no"), "{}", err),
+            status => panic!("unexpected status {:?}", status),
+        }
+        match &results[1].status {
+            Status::Error(err) => assert!(err.starts_with("timed out"), "{}", err),
+            status => panic!("unexpected status {:?}", status),
+        }
+        assert_eq!(to_junit(&results).matches("<error ").count(), 2);
+    }
+
+    #[test]
+    fn test_verify_unsolved() {
+        let input = "target area: x=20..30, y=-10..-5";
+        let path = env::temp_dir().join(format!("aoc-verify-unsolved-{}.json", process::id()));
+        let store = AnswerStore::load(&path).unwrap();
+        let results = verify(
+            &Registry::new(2021),
+            &[17],
+            &store,
+            |_| Ok(input.to_string()),
+            None,
+        );
+        assert_eq!(results[0].status, Status::Unrecorded);
+        assert_eq!(results[1].status, Status::Unsolved);
+        assert_eq!(counts(&results), (0, 0, 0, 1, 1));
+    }
+
+    #[test]
+    fn test_to_junit() {
+        let results = vec![
+            CaseResult {
+                day: 1,
+                part: 1,
+                answer: Some(Answer::Unsigned(7)),
+                status: Status::Pass,
+            },
+            CaseResult {
+                day: 1,
+                part: 2,
+                answer: Some(Answer::Unsigned(4)),
+                status: Status::Fail {
+                    expected: "5".to_string(),
+                },
+            },
+            CaseResult {
+                day: 2,
+                part: 1,
+                answer: None,
+                status: Status::Error("bad <input>".to_string()),
+            },
+        ];
+        let xml = to_junit(&results);
+        assert!(xml.contains("tests=\"3\" failures=\"1\" errors=\"1\" skipped=\"0\""));
+        assert!(xml.contains("<testcase classname=\"day01\" name=\"part1\"/>"));
+        assert!(xml.contains("<failure message=\"got 4, expected 5\"/>"));
+        assert!(xml.contains("<error message=\"bad &lt;input&gt;\"/>"));
+    }
+}
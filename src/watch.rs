@@ -0,0 +1,365 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache::Cache,
+    cli::{self, PartSelector},
+    input::{self, InputSource},
+    runner::{self, Report, Status},
+    solution::Registry,
+};
+
+/// How often `watch_day` checks for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Decides which files in a watched directory to watch, by name.
+type Filter = Box<dyn Fn(&str) -> bool>;
+
+/// Polls files for changes. There's no portable way to be notified of changes
+/// without pulling in a platform-specific dependency, and polling a handful of
+/// files every half a second is cheap.
+pub struct Watcher {
+    /// Directories whose files matching a filter are watched, so that new
+    /// files are noticed too.
+    dirs: Vec<(PathBuf, Filter)>,
+    stamps: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+    /// A watcher with no directories to watch yet.
+    pub fn new() -> Watcher {
+        Watcher {
+            dirs: vec![],
+            stamps: BTreeMap::new(),
+        }
+    }
+
+    /// Watches the files in `dir` whose names `filter` accepts, including ones
+    /// created later. The directory doesn't need to exist yet.
+    pub fn watch_dir(&mut self, dir: &Path, filter: impl Fn(&str) -> bool + 'static) -> Result<()> {
+        self.dirs.push((dir.to_path_buf(), Box::new(filter)));
+        self.stamps = self.scan()?;
+        Ok(())
+    }
+
+    /// Returns the files that have been created, modified or removed since
+    /// the last call.
+    pub fn changed(&mut self) -> Result<Vec<PathBuf>> {
+        let stamps = self.scan()?;
+        let mut changed: Vec<PathBuf> = stamps
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.stamps
+                .keys()
+                .filter(|path| !stamps.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        self.stamps = stamps;
+        Ok(changed)
+    }
+
+    fn scan(&self) -> Result<BTreeMap<PathBuf, SystemTime>> {
+        let mut stamps = BTreeMap::new();
+        for (dir, filter) in &self.dirs {
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed to read {}", dir.display()))
+                }
+            };
+            for entry in entries {
+                let entry = entry?;
+                if !filter(&entry.file_name().to_string_lossy()) {
+                    continue;
+                }
+                // The file may have been removed since it was listed.
+                if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                    stamps.insert(entry.path(), modified);
+                }
+            }
+        }
+        Ok(stamps)
+    }
+}
+
+impl Default for Watcher {
+    fn default() -> Watcher {
+        Watcher::new()
+    }
+}
+
+/// Passes the history on to the new process when restarting after a rebuild.
+const HISTORY_ENV_VAR_NAME: &str = "AOC_WATCH_HISTORY";
+
+/// Remembers the answers from the previous run, so that each run can be
+/// reported as a diff against it.
+#[derive(Default, Deserialize, Serialize)]
+pub struct History {
+    /// Answers by input source, then part.
+    answers: BTreeMap<String, BTreeMap<u32, String>>,
+}
+
+impl History {
+    /// The history handed over by the process this one replaced, if any.
+    pub fn from_env() -> History {
+        env::var(HISTORY_ENV_VAR_NAME)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Formats one line per part with its status, timing and answer, noting
+    /// where the answer differs from the previous run's.
+    pub fn report(&mut self, report: &Report) -> String {
+        let mut out = String::new();
+        for r in &report.results {
+            let source = r.input_source.clone().unwrap_or_default();
+            write!(out, "Part {} ({}) in {:.2?}: ", r.part, source, r.duration).unwrap();
+            let answer = match (&r.answer, &r.error) {
+                (Some(answer), _) => answer.to_string(),
+                (None, error) => {
+                    let status = format!("{:?}", r.status).to_uppercase();
+                    writeln!(out, "{}: {}", status, error.as_deref().unwrap_or("")).unwrap();
+                    continue;
+                }
+            };
+            let status = match r.status {
+                Status::Mismatch => format!(
+                    " MISMATCH, expected {}",
+                    r.expected.as_deref().unwrap_or("")
+                ),
+                Status::Unsolved => String::new(),
+                _ => " ok".to_string(),
+            };
+            let previous = self
+                .answers
+                .entry(source)
+                .or_default()
+                .insert(r.part, answer.clone());
+            let diff = match previous {
+                Some(previous) if previous == answer => " (unchanged)".to_string(),
+                Some(previous) => format!(" (was {})", one_line(&previous)),
+                None => String::new(),
+            };
+            writeln!(out, "{}{}{}", one_line(&answer), status, diff).unwrap();
+        }
+        out.trim_end().to_string()
+    }
+}
+
+/// Reruns the day against its examples and puzzle input whenever its cached
+/// files change, and rebuilds and restarts when the source changes. Only
+/// returns on error.
+pub fn watch_day(
+    registry: &Registry,
+    cache: &Cache,
+    day: u32,
+    part: PartSelector,
+    options: &runner::Options,
+) -> Result<()> {
+    // Download the input up front, so that doing so doesn't count as a change.
+    input::get_input(cache, day).context("failed to load input")?;
+    let prefix = format!("day{:02}.", day);
+    let mut inputs = Watcher::new();
+    inputs.watch_dir(&cache.dir()?, move |name| name.starts_with(&prefix))?;
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut sources = Watcher::new();
+    for dir in [
+        manifest_dir.join("src"),
+        manifest_dir.join("src").join(format!("y{}", cache.year())),
+    ] {
+        sources.watch_dir(&dir, |name| name.ends_with(".rs"))?;
+    }
+
+    let problems = cli::problems(&[day], part);
+    let load_inputs = |day| input::load_all(Some(cache), &InputSource::Puzzle, None, true, day);
+    let mut history = History::from_env();
+    loop {
+        let report = runner::run(registry, &problems, load_inputs, options);
+        println!(
+            "{}\n\nWatching for changes, press Ctrl-C to stop.",
+            history.report(&report)
+        );
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if !sources.changed()?.is_empty() {
+                println!("\nSource changed, rebuilding...");
+                rebuild_and_restart(manifest_dir, &history)?;
+                println!("Build failed, still watching.");
+                continue;
+            }
+            let changed = inputs.changed()?;
+            if !changed.is_empty() {
+                let names: Vec<_> = changed
+                    .iter()
+                    .filter_map(|path| path.file_name())
+                    .map(|name| name.to_string_lossy())
+                    .collect();
+                println!("\n{} changed, rerunning...", names.join(", "));
+                break;
+            }
+        }
+    }
+}
+
+/// Rebuilds the crate at `manifest_dir` the same way this binary was built,
+/// and if that succeeds, replaces this process with the new binary, run with
+/// the same arguments and handed `history`. Returns if the build fails, so
+/// that the caller can carry on with the old binary until the source is fixed.
+pub fn rebuild_and_restart(manifest_dir: &Path, history: &History) -> Result<()> {
+    // Look this up first, since on Linux it's reported as deleted once the
+    // build has replaced it.
+    let exe = env::current_exe().context("can't find the current executable")?;
+    let mut build = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    build
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"));
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    let status = build.status().context("failed to run cargo build")?;
+    if !status.success() {
+        return Ok(());
+    }
+
+    let mut restart = Command::new(exe);
+    restart
+        .args(env::args_os().skip(1))
+        .env(HISTORY_ENV_VAR_NAME, serde_json::to_string(history)?);
+    restart_with(restart)
+}
+
+#[cfg(unix)]
+fn restart_with(mut command: Command) -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    Err(anyhow!(command.exec()).context("failed to restart"))
+}
+
+#[cfg(not(unix))]
+fn restart_with(mut command: Command) -> Result<()> {
+    // There's no exec, so run the new binary to completion instead.
+    let status = command.status().context("failed to restart")?;
+    std::process::exit(status.code().unwrap_or(1))
+}
+
+/// Puts multi-line answers like grids on their own lines.
+fn one_line(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}\n", answer)
+    } else {
+        answer.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs, process,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{History, Watcher};
+    use crate::{
+        answer::Answer,
+        runner::{PartResult, Report, Status},
+    };
+
+    #[test]
+    fn test_watcher() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        let mut watcher = Watcher::new();
+        watcher
+            .watch_dir(&dir, |name| name.starts_with("day01."))
+            .unwrap();
+        assert!(watcher.changed().unwrap().is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day01.txt"), "1\n").unwrap();
+        fs::write(dir.join("day02.txt"), "2\n").unwrap();
+        assert_eq!(watcher.changed().unwrap(), vec![dir.join("day01.txt")]);
+        assert!(watcher.changed().unwrap().is_empty());
+
+        let file = fs::File::options()
+            .write(true)
+            .open(dir.join("day01.txt"))
+            .unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(watcher.changed().unwrap(), vec![dir.join("day01.txt")]);
+
+        fs::remove_file(dir.join("day01.txt")).unwrap();
+        assert_eq!(watcher.changed().unwrap(), vec![dir.join("day01.txt")]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn report(answers: &[(&str, Answer, Option<&str>)]) -> Report {
+        let results = answers
+            .iter()
+            .map(|(source, answer, expected)| PartResult {
+                day: 1,
+                part: 1,
+                title: "Sonar Sweep",
+                status: match expected {
+                    Some(e) if !answer.matches(e) => Status::Mismatch,
+                    _ => Status::Ok,
+                },
+                answer: Some(answer.clone()),
+                expected: expected.map(String::from),
+                error: None,
+                duration: Duration::from_millis(2),
+                input_hash: None,
+                input_source: Some(source.to_string()),
+                alloc: None,
+            })
+            .collect();
+        Report {
+            results,
+            parses: vec![],
+            total_duration: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = History::default();
+        let first = report(&[
+            ("example 1", Answer::Unsigned(7), Some("7")),
+            ("puzzle input", Answer::Unsigned(1500), None),
+        ]);
+        assert_eq!(
+            history.report(&first),
+            "\
+Part 1 (example 1) in 2.00ms: 7 ok
+Part 1 (puzzle input) in 2.00ms: 1500 ok"
+        );
+
+        let second = report(&[
+            ("example 1", Answer::Unsigned(8), Some("7")),
+            ("puzzle input", Answer::Unsigned(1500), None),
+        ]);
+        assert_eq!(
+            history.report(&second),
+            "\
+Part 1 (example 1) in 2.00ms: 8 MISMATCH, expected 7 (was 7)
+Part 1 (puzzle input) in 2.00ms: 1500 ok (unchanged)"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Day 1: Sonar Sweep, <https://adventofcode.com/2021/day/1>.
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Sonar Sweep";
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(depths: &Vec<i32>) -> Result<Answer> {
+        part1(depths)
+    }
+
+    fn part2(depths: &Vec<i32>) -> Result<Answer> {
+        part2(depths)
+    }
+}
+
+/// Counts the number of times a depth measurement increases from the previous
+/// one.
+pub fn part1(depths: &[i32]) -> Result<Answer> {
+    let mut num_increasing = 0usize;
+    let mut prev = -1;
+    for &depth in depths {
+        if prev >= 0 && depth > prev {
+            num_increasing += 1;
+        }
+        prev = depth
+    }
+    Ok(num_increasing.into())
+}
+
+/// Counts the number of times a depth measurement increases from one window of
+/// three entries to the next.
+pub fn part2(depths: &[i32]) -> Result<Answer> {
+    let window_size = 3;
+    let mut num_increasing = 0usize;
+    let mut prev_sum = -1;
+    for window in depths.windows(window_size) {
+        let sum = window.iter().sum();
+        if prev_sum >= 0 && sum > prev_sum {
+            num_increasing += 1;
+        }
+        prev_sum = sum;
+    }
+    Ok(num_increasing.into())
+}
+
+/// Parses the sonar sweep's depth measurements, one per line.
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Source::new(Day01::DAY, input);
+    input.trim().lines().map(|l| src.number(l)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "7");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "5");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("199\n2x0\n").unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (1, 2, 1));
+        assert_eq!(err.found, "2x0");
+        assert_eq!(err.expected, "a number");
+    }
+}
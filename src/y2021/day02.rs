@@ -0,0 +1,114 @@
+//! Day 2: Dive!, <https://adventofcode.com/2021/day/2>.
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Dive!";
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Vec<Move>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(moves: &Vec<Move>) -> Result<Answer> {
+        part1(moves)
+    }
+
+    fn part2(moves: &Vec<Move>) -> Result<Answer> {
+        part2(moves)
+    }
+}
+
+/// Follows the course with "down" and "up" changing the depth, and returns
+/// the final horizontal position multiplied by the final depth.
+pub fn part1(moves: &[Move]) -> Result<Answer> {
+    let mut horiz = 0;
+    let mut depth = 0;
+    for mv in moves {
+        match mv.direction.as_str() {
+            "forward" => horiz += mv.count,
+            "down" => depth += mv.count,
+            "up" => depth -= mv.count,
+            _ => return Err(anyhow::anyhow!("Invalid direction: {}", mv.direction)),
+        }
+    }
+    Ok((horiz * depth).into())
+}
+
+/// Follows the course with "down" and "up" changing the aim instead, and
+/// returns the final horizontal position multiplied by the final depth.
+pub fn part2(moves: &[Move]) -> Result<Answer> {
+    let (mut horiz, mut depth, mut aim) = (0, 0, 0);
+
+    for mv in moves {
+        match mv.direction.as_str() {
+            "forward" => {
+                horiz += mv.count;
+                depth += aim * mv.count;
+            }
+            "down" => aim += mv.count,
+            "up" => aim -= mv.count,
+            _ => return Err(anyhow::anyhow!("Invalid direction: {}", mv.direction)),
+        }
+    }
+    Ok((horiz * depth).into())
+}
+
+pub struct Move {
+    direction: String,
+    count: i32,
+}
+
+/// Parses the planned course, one command like "forward 5" per line.
+pub fn parse_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    let src = Source::new(Day02::DAY, input);
+    input
+        .trim()
+        .lines()
+        .map(|l| {
+            let (direction, count) = l
+                .split_once(' ')
+                .ok_or_else(|| src.error(l, "a direction and a count"))?;
+            Ok(Move {
+                direction: direction.into(),
+                count: src.number(count)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "150");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "900");
+    }
+}
@@ -1,7 +1,36 @@
+//! Day 3: Binary Diagnostic, <https://adventofcode.com/2021/day/3>.
+
 use std::{collections::HashMap, ops::Index};
 
 use anyhow::{Context, Result};
 
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Binary Diagnostic";
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<char>>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(lines: &Vec<Vec<char>>) -> Result<Answer> {
+        part1(lines)
+    }
+
+    fn part2(lines: &Vec<Vec<char>>) -> Result<Answer> {
+        part2(lines)
+    }
+}
+
 /// Given a list of binary numbers, produces two new numbers of the same length:
 /// one where each binary digit is the *most* common digit at the corresponding
 /// index across all the input numbers, and another where each binary digit is
@@ -9,13 +38,12 @@ use anyhow::{Context, Result};
 /// numbers.
 ///
 /// Then returns the product of the resulting two numbres as a decimal.
-pub fn part1(input: &str) -> Result<String> {
-    let lines = input.trim().split_whitespace();
+pub fn part1(lines: &[Vec<char>]) -> Result<Answer> {
     let mut length = 0;
     let mut ones_counts: HashMap<usize, i64> = HashMap::new();
     for line in lines {
         length += 1;
-        for (i, ch) in line.chars().enumerate() {
+        for (i, &ch) in line.iter().enumerate() {
             let count = ones_counts.entry(i).or_insert(0);
             if ch == '1' {
                 *count += 1;
@@ -35,16 +63,12 @@ pub fn part1(input: &str) -> Result<String> {
             least_common |= 1;
         }
     }
-    Ok((most_common * least_common).to_string())
+    Ok((most_common * least_common).into())
 }
 
-pub fn part2(input: &str) -> Result<String> {
-    let lines: Vec<Vec<char>> = input
-        .trim()
-        .split_whitespace()
-        .map(|s| s.chars().collect::<Vec<char>>())
-        .collect();
-
+/// Computes the oxygen generator and CO2 scrubber ratings by repeatedly
+/// filtering on the most and least common bit, and returns their product.
+pub fn part2(lines: &[Vec<char>]) -> Result<Answer> {
     let mut most_common = lines.to_vec();
     let mut i = 0;
     while most_common.len() > 1 {
@@ -90,7 +114,19 @@ pub fn part2(input: &str) -> Result<String> {
     let m: String = most_common.index(0).iter().collect();
     let l: String = least_common.index(0).iter().collect();
 
-    Ok((binary_string_to_int(&m)? * binary_string_to_int(&l)?).to_string())
+    Ok((binary_string_to_int(&m)? * binary_string_to_int(&l)?).into())
+}
+
+/// Parses the diagnostic report, one binary number per line.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let src = Source::new(Day03::DAY, input);
+    input
+        .split_whitespace()
+        .map(|s| match s.char_indices().find(|&(_, c)| c != '0' && c != '1') {
+            Some((i, c)) => Err(src.error(&s[i..i + c.len_utf8()], "a binary digit")),
+            None => Ok(s.chars().collect()),
+        })
+        .collect()
 }
 
 fn binary_string_to_int(s: &str) -> Result<isize> {
@@ -99,7 +135,7 @@ fn binary_string_to_int(s: &str) -> Result<isize> {
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::{parse_input, part1, part2};
 
     const TEST_INPUT: &str = "
 00100
@@ -118,11 +154,13 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT).unwrap(), "198");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "198");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT).unwrap(), "230");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "230");
     }
 }
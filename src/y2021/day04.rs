@@ -0,0 +1,230 @@
+//! Day 4: Giant Squid, <https://adventofcode.com/2021/day/4>.
+
+use anyhow::Result;
+use tracing::debug;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::{Solution, SolveError},
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Giant Squid";
+    type Input = Bingo;
+
+    fn parse(input: &str) -> Result<Bingo> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(bingo: &Bingo) -> Result<Answer> {
+        part1(bingo)
+    }
+
+    fn part2(bingo: &Bingo) -> Result<Answer> {
+        part2(bingo)
+    }
+}
+
+// Length of each side of the bingo board.
+const BOARD_SIZE: usize = 5;
+
+/// The numbers to be drawn, in order, and the boards they're marked on.
+pub struct Bingo {
+    numbers: Vec<i32>,
+    boards: Vec<Board>,
+}
+
+/// Computes the score of the first board to win.
+pub fn part1(bingo: &Bingo) -> Result<Answer> {
+    let mut boards = bingo.boards.clone();
+    for &num in &bingo.numbers {
+        for (i, board) in boards.iter_mut().enumerate() {
+            board.apply_num(num);
+            if board.has_bingo() {
+                let score = num * board.unmarked_sum();
+                debug!(board = i, num, score, "first board won");
+                return Ok(score.into());
+            }
+        }
+    }
+    Err(SolveError::NoWinner.into())
+}
+
+/// Computes the score of the last board to win.
+pub fn part2(bingo: &Bingo) -> Result<Answer> {
+    let mut boards = bingo.boards.clone();
+    for &num in &bingo.numbers {
+        for board in &mut boards {
+            board.apply_num(num);
+        }
+        let last_board_sum = boards
+            .last()
+            .ok_or(SolveError::TooFew {
+                what: "boards",
+                needed: 1,
+                found: 0,
+            })?
+            .unmarked_sum();
+        let len = boards.len();
+        // Remove all boards that now have a bingo. If there are no longer any
+        // boards without a bingo, return the score of the last board to get a
+        // bingo.
+        let new_boards: Vec<Board> = boards.into_iter().filter(|b| !b.has_bingo()).collect();
+        if new_boards.len() < len {
+            debug!(num, won = len - new_boards.len(), left = new_boards.len(), "boards won");
+        }
+        if new_boards.is_empty() {
+            return Ok((num * last_board_sum).into());
+        }
+        boards = new_boards;
+    }
+    Err(SolveError::NoLastWinner { left: boards.len() }.into())
+}
+
+/// Parses the numbers to draw followed by the bingo boards.
+pub fn parse_input(input: &str) -> Result<Bingo, ParseError> {
+    let src = Source::new(Day04::DAY, input);
+    let mut groups = input.trim().split("\n\n");
+    // Splitting always yields at least one group, even if it's empty.
+    let numbers = groups
+        .next()
+        .unwrap_or_default()
+        .split(',')
+        .map(|x| src.number(x))
+        .collect::<Result<_, _>>()?;
+    let mut boards = vec![];
+    for group in groups {
+        let mut grid = [0; BOARD_SIZE * BOARD_SIZE];
+        let mut nums = group.split_whitespace();
+        for cell in grid.iter_mut() {
+            let num = nums.next().ok_or_else(|| {
+                src.error_after(group, format!("{} numbers", BOARD_SIZE * BOARD_SIZE))
+            })?;
+            *cell = src.number(num)?;
+        }
+        if let Some(extra) = nums.next() {
+            return Err(src.error(extra, "the end of the board"));
+        }
+        boards.push(Board {
+            grid,
+            chosen: [false; BOARD_SIZE * BOARD_SIZE],
+        });
+    }
+    Ok(Bingo { numbers, boards })
+}
+
+#[derive(Clone, Debug)]
+struct Board {
+    grid: [i32; BOARD_SIZE * BOARD_SIZE],
+    chosen: [bool; BOARD_SIZE * BOARD_SIZE],
+}
+
+impl Board {
+    fn has_bingo(&self) -> bool {
+        for row in 0..BOARD_SIZE {
+            if (0..BOARD_SIZE)
+                .all(|col| self.chosen[self.index(row, col)])
+            {
+                return true;
+            }
+        }
+        for col in 0..BOARD_SIZE {
+            if (0..BOARD_SIZE)
+                .all(|row| self.chosen[self.index(row, col)])
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        row * BOARD_SIZE + col
+    }
+
+    fn unmarked_sum(&self) -> i32 {
+        self.chosen
+            .into_iter()
+            .enumerate()
+            .filter(|(_, chosen)| !*chosen)
+            .map(|(i, _)| self.grid[i])
+            .sum()
+    }
+
+    fn apply_num(&mut self, choice: i32) {
+        for (i, num) in self.grid.into_iter().enumerate() {
+            if num == choice {
+                self.chosen[i] = true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+    use crate::solution::SolveError;
+
+    const TEST_INPUT: &str = "
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "4512");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "1924");
+    }
+
+    #[test]
+    fn test_no_winner() {
+        let board = (1..=25).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let input = parse_input(&format!("1,2,3\n\n{}", board)).unwrap();
+        let err = part1(&input).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&SolveError::NoWinner));
+        let err = part2(&input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&SolveError::NoLastWinner { left: 1 })
+        );
+
+        let input = parse_input("1,2,3").unwrap();
+        let err = part2(&input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&SolveError::TooFew {
+                what: "boards",
+                needed: 1,
+                found: 0,
+            })
+        );
+    }
+}
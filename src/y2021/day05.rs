@@ -0,0 +1,173 @@
+//! Day 5: Hydrothermal Venture, <https://adventofcode.com/2021/day/5>.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Hydrothermal Venture";
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Vec<Line>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(lines: &Vec<Line>) -> Result<Answer> {
+        part1(lines)
+    }
+
+    fn part2(lines: &Vec<Line>) -> Result<Answer> {
+        part2(lines)
+    }
+}
+
+/// Counts the number of points at which two lines intersect, only counting
+/// horizontal and vertical lines.
+pub fn part1(lines: &[Line]) -> Result<Answer> {
+    let overlaps = count_overlaps(
+        lines
+            .iter()
+            .filter(|line| line.horizontal() || line.vertical()),
+    );
+    Ok(overlaps.into())
+}
+
+/// Counts the number of points at which two lines intersect, including diagonal
+/// lines.
+pub fn part2(lines: &[Line]) -> Result<Answer> {
+    let overlaps = count_overlaps(lines);
+    Ok(overlaps.into())
+}
+
+fn count_overlaps<'a>(lines: impl IntoIterator<Item = &'a Line>) -> usize {
+    let mut counts = HashMap::new();
+    for line in lines {
+        for pt in line.points() {
+            let count = counts.entry(pt).or_insert(0);
+            *count += 1;
+        }
+    }
+    counts.into_values().filter(|v| *v > 1).count()
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct Line {
+    start: Point,
+    end: Point,
+}
+
+impl Line {
+    fn horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    fn vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    fn points(&self) -> Vec<Point> {
+        let mut points = vec![];
+        let (mut x, mut y) = (self.start.x, self.start.y);
+        let x_diff = norm_diff(self.start.x, self.end.x);
+        let y_diff = norm_diff(self.start.y, self.end.y);
+        while x != self.end.x || y != self.end.y {
+            points.push(Point { x, y });
+            x += x_diff;
+            y += y_diff;
+        }
+        points.push(self.end.clone());
+        points
+    }
+}
+
+fn norm_diff(start: i32, end: i32) -> i32 {
+    let diff = end - start;
+    if diff == 0 {
+        0
+    } else {
+        diff / diff.abs()
+    }
+}
+
+/// Parses the lines of hydrothermal vents, one like "0,9 -> 5,9" per line.
+pub fn parse_input(input: &str) -> Result<Vec<Line>, ParseError> {
+    let src = Source::new(Day05::DAY, input);
+    let re = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let caps = re
+                .captures(line)
+                .ok_or_else(|| src.error(line, "a line like \"0,9 -> 5,9\""))?;
+            let num = |group| src.number(&caps[group]);
+            Ok(Line {
+                start: Point {
+                    x: num(1)?,
+                    y: num(2)?,
+                },
+                end: Point {
+                    x: num(3)?,
+                    y: num(4)?,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "5");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "12");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("0,9 -> 5,9\n8,0 => 0,8\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found, "8,0 => 0,8");
+        let err = parse_input("0,9 -> 99999999999,9").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 8));
+        assert_eq!(err.expected, "a number");
+    }
+}
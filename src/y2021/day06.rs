@@ -0,0 +1,95 @@
+//! Day 6: Lanternfish, <https://adventofcode.com/2021/day/6>.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Lanternfish";
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(fish: &Vec<i32>) -> Result<Answer> {
+        part1(fish)
+    }
+
+    fn part2(fish: &Vec<i32>) -> Result<Answer> {
+        part2(fish)
+    }
+}
+
+const VALUE_AFTER_BIRTH: i32 = 6;
+const NEW_FISH_VALUE: i32 = 8;
+
+/// Counts the lanternfish after 80 days.
+pub fn part1(fish: &[i32]) -> Result<Answer> {
+    Ok(population_after_days(fish, 80).into())
+}
+
+/// Counts the lanternfish after 256 days.
+pub fn part2(fish: &[i32]) -> Result<Answer> {
+    Ok(population_after_days(fish, 256).into())
+}
+
+fn population_after_days(fish: &[i32], days: i32) -> usize {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for &f in fish {
+        incr_count(&mut counts, f, 1);
+    }
+    for _ in 0..days {
+        let mut new_counts = HashMap::new();
+        for (f, count) in counts.into_iter() {
+            if f > 0 {
+                incr_count(&mut new_counts, f - 1, count);
+            } else {
+                incr_count(&mut new_counts, NEW_FISH_VALUE, count);
+                incr_count(&mut new_counts, VALUE_AFTER_BIRTH, count);
+            }
+        }
+        counts = new_counts;
+    }
+    counts.values().sum()
+}
+
+fn incr_count(counter: &mut HashMap<i32, usize>, k: i32, amount: usize) {
+    let val = counter.entry(k).or_insert(0);
+    *val += amount;
+}
+
+/// Parses the comma-separated internal timers of the lanternfish.
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Source::new(Day06::DAY, input);
+    input.trim().split(',').map(|s| src.number(s)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "3,4,3,1,2";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "5934");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "26984457539");
+    }
+}
@@ -0,0 +1,95 @@
+//! Day 7: The Treachery of Whales, <https://adventofcode.com/2021/day/7>.
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "The Treachery of Whales";
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(positions: &Vec<i32>) -> Result<Answer> {
+        part1(positions)
+    }
+
+    fn part2(positions: &Vec<i32>) -> Result<Answer> {
+        part2(positions)
+    }
+}
+
+/// Computes the least fuel needed to line the crabs up, when each step costs
+/// one unit of fuel.
+pub fn part1(positions: &[i32]) -> Result<Answer> {
+    let mut nums = positions.to_vec();
+    let med = median(&mut nums);
+    let fuel: i32 = nums.iter().map(|x| (med - x).abs()).sum();
+    Ok(fuel.into())
+}
+
+/// Computes the least fuel needed to line the crabs up, when each step costs
+/// one more unit than the last.
+pub fn part2(nums: &[i32]) -> Result<Answer> {
+    let max = *nums.iter().max().unwrap();
+    let min = *nums.iter().min().unwrap();
+
+    let mut min_fuel: Option<i32> = None;
+    for target in min..=max {
+        let fuel = nums.iter().map(|&num| sum_1_to((target - num).abs())).sum();
+        min_fuel = match min_fuel {
+            None => Some(fuel),
+            Some(current_min) => Some(current_min.min(fuel)),
+        };
+    }
+    Ok(min_fuel.unwrap().into())
+}
+
+fn sum_1_to(n: i32) -> i32 {
+    (n * (n + 1)) / 2
+}
+
+fn median(nums: &mut [i32]) -> i32 {
+    nums.sort_unstable();
+    let len = nums.len();
+    if len % 2 == 1 {
+        nums[len / 2]
+    } else {
+        (nums[len / 2 - 1] + nums[len / 2]) / 2
+    }
+}
+
+/// Parses the comma-separated horizontal positions of the crabs.
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Source::new(Day07::DAY, input);
+    input.trim().split(',').map(|s| src.number(s)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "37");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "168");
+    }
+}
@@ -0,0 +1,220 @@
+//! Day 8: Seven Segment Search, <https://adventofcode.com/2021/day/8>.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use lazy_static::lazy_static;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::{SolveError, Solution},
+};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Seven Segment Search";
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Vec<Entry>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(entries: &Vec<Entry>) -> Result<Answer> {
+        part1(entries)
+    }
+
+    fn part2(entries: &Vec<Entry>) -> Result<Answer> {
+        part2(entries)
+    }
+}
+
+lazy_static! {
+    // Length of each digit that's represented by a unique number of segments.
+    static ref UNIQUE_DIGIT_LENGTHS: Vec<usize> = vec![2, 3, 4, 7];
+    static ref DIGIT_SEGMENTS: HashMap<&'static str, i32> = {
+        let mut map = HashMap::new();
+        map.insert("abcefg", 0);
+        map.insert("cf", 1);
+        map.insert("acdeg", 2);
+        map.insert("acdfg", 3);
+        map.insert("bcdf", 4);
+        map.insert("abdfg", 5);
+        map.insert("abdefg", 6);
+        map.insert("acf", 7);
+        map.insert("abcdefg", 8);
+        map.insert("abcdfg", 9);
+        map
+    };
+    #[derive(Debug)]
+    static ref SEGMENT_LENGTH_SIGNATURES: HashMap<String, char> = {
+        let digits = DIGIT_SEGMENTS.keys().copied().collect();
+        segment_digit_lengths(digits)
+    };
+}
+
+fn segment_digit_lengths(digits: Vec<&str>) -> HashMap<String, char> {
+    let mut map: HashMap<char, HashMap<usize, usize>> = HashMap::new();
+    for digit in digits {
+        for c in digit.chars() {
+            let counts = map.entry(c).or_default();
+            let count = counts.entry(digit.len()).or_default();
+            *count += 1;
+        }
+    }
+    let mut inverted_map = HashMap::new();
+    for (char, lengths) in map.iter() {
+        let mut key_parts = lengths
+            .iter()
+            .map(|(length, count)| format!("{}:{}", length, count))
+            .collect::<Vec<String>>();
+        key_parts.sort();
+        inverted_map.insert(key_parts.join(","), *char);
+    }
+    inverted_map
+}
+
+/// One line of the input: the ten unique signal patterns, and the four digits
+/// of the output value.
+#[derive(Debug)]
+pub struct Entry {
+    patterns: Vec<String>,
+    outputs: Vec<String>,
+}
+
+/// Counts the output digits that use a unique number of segments, i.e. 1, 4,
+/// 7 and 8.
+pub fn part1(entries: &[Entry]) -> Result<Answer> {
+    let count = entries
+        .iter()
+        .map(|entry| {
+            entry
+                .outputs
+                .iter()
+                .filter(|digit| UNIQUE_DIGIT_LENGTHS.contains(&digit.len()))
+                .count()
+        })
+        .sum::<usize>();
+    Ok(count.into())
+}
+
+/// Takes advantange of the fact that for every line segment that makes up a part
+/// of a digit, that line segment has a unique "signature". The signature is
+/// defined by a counter that, for each number of per-digit segments, maps to the
+/// number of digits that have that number of segments.
+pub fn part2(entries: &[Entry]) -> Result<Answer> {
+    let mut sum = 0;
+    for (i, entry) in entries.iter().enumerate() {
+        let unwired = || SolveError::Unwired { entry: i + 1 };
+        let patterns: Vec<_> = entry.patterns.iter().map(String::as_str).collect();
+        let mut translation: HashMap<char, char> = HashMap::new();
+
+        for (signature, c) in segment_digit_lengths(patterns) {
+            let segment = SEGMENT_LENGTH_SIGNATURES
+                .get(&signature)
+                .ok_or_else(unwired)?;
+            translation.insert(c, *segment);
+        }
+
+        let mut line_sum = 0;
+        for val in &entry.outputs {
+            let mut translated = val
+                .chars()
+                .map(|c| translation.get(&c).copied().ok_or_else(unwired))
+                .collect::<Result<Vec<char>, _>>()?;
+            translated.sort_unstable();
+            let val = *DIGIT_SEGMENTS
+                .get(translated.iter().collect::<String>().as_str())
+                .ok_or_else(unwired)?;
+            line_sum *= 10;
+            line_sum += val;
+        }
+        sum += line_sum;
+    }
+    Ok(sum.into())
+}
+
+/// Parses each display's ten signal patterns and four output digits.
+pub fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let src = Source::new(Day08::DAY, input);
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let (patterns, outputs) = line
+                .split_once(" | ")
+                .ok_or_else(|| src.error(line, "patterns and outputs separated by \" | \""))?;
+            let words = |half: &str, count, expected| {
+                let segment = |c: char| c.is_whitespace() || ('a'..='g').contains(&c);
+                if let Some((i, c)) = half.char_indices().find(|&(_, c)| !segment(c)) {
+                    let bad = &half[i..i + c.len_utf8()];
+                    return Err(src.error(bad, "a segment from a to g"));
+                }
+                let words: Vec<String> = half.split_whitespace().map(String::from).collect();
+                if words.len() == count {
+                    Ok(words)
+                } else {
+                    Err(src.error(half, expected))
+                }
+            };
+            Ok(Entry {
+                patterns: words(patterns, 10, "ten signal patterns")?,
+                outputs: words(outputs, 4, "four output digits")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+    use crate::solution::SolveError;
+
+    const TEST_INPUT: &str = "
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "26");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "61229");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_segment() {
+        let err = parse_input("z | z\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        let line = TEST_INPUT.trim().lines().next().unwrap().replacen("cgeb", "cgxb", 1);
+        let err = parse_input(&line).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 28));
+        assert_eq!(err.found, "x");
+        assert_eq!(err.expected, "a segment from a to g");
+    }
+
+    #[test]
+    fn test_part2_unwired() {
+        let line = format!("{} | ab ab ab ab", ["ab"; 10].join(" "));
+        let input = parse_input(&line).unwrap();
+        let err = part2(&input).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&SolveError::Unwired { entry: 1 }));
+    }
+}
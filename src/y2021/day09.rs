@@ -0,0 +1,124 @@
+//! Day 9: Smoke Basin, <https://adventofcode.com/2021/day/9>.
+
+use std::collections::{BinaryHeap, HashSet};
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    grid::{Grid, Neighbors},
+    parse::{ParseError, Source},
+    solution::{Solution, SolveError},
+};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Smoke Basin";
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Grid<u32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(grid: &Grid<u32>) -> Result<Answer> {
+        part1(grid)
+    }
+
+    fn part2(grid: &Grid<u32>) -> Result<Answer> {
+        part2(grid)
+    }
+}
+
+/// Sums the risk levels of the low points, those lower than every adjacent
+/// point.
+pub fn part1(grid: &Grid<u32>) -> Result<Answer> {
+    let mut sum = 0;
+    for (pos, val) in grid.iter() {
+        let mut neighbors = grid.neighbors(pos, Neighbors::Four).peekable();
+        if neighbors.peek().is_some() && neighbors.all(|n| grid[n] > *val) {
+            sum += val + 1;
+        }
+    }
+
+    Ok(sum.into())
+}
+
+/// Uses DFS to explore each "basin" (enclosed group of numbers < 9) and returns
+/// the product of the sizes of the three largest basins.
+pub fn part2(grid: &Grid<u32>) -> Result<Answer> {
+    let mut visited = HashSet::new();
+    let mut basins = BinaryHeap::new();
+    for start in grid.positions() {
+        let mut basin_size = 0;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            if visited.contains(&pos) || grid[pos] == 9 {
+                continue;
+            }
+            visited.insert(pos);
+            basin_size += 1;
+            stack.extend(grid.neighbors(pos, Neighbors::Four));
+        }
+        if basin_size > 0 {
+            basins.push(basin_size);
+        }
+    }
+    if basins.len() < 3 {
+        return Err(SolveError::TooFew {
+            what: "basins",
+            needed: 3,
+            found: basins.len(),
+        }
+        .into());
+    }
+    let res: usize = (0..3).filter_map(|_| basins.pop()).product();
+    Ok(res.into())
+}
+
+/// Parses the height of each point on the cave floor.
+pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse_digits(&Source::new(Day09::DAY, input), input.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+    use crate::solution::SolveError;
+
+    const TEST_INPUT: &str = "
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "15");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "1134");
+    }
+
+    #[test]
+    fn test_too_few_basins() {
+        let input = parse_input("191\n999\n").unwrap();
+        let err = part2(&input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&SolveError::TooFew {
+                what: "basins",
+                needed: 3,
+                found: 2,
+            })
+        );
+    }
+}
@@ -1,12 +1,41 @@
+//! Day 10: Syntax Scoring, <https://adventofcode.com/2021/day/10>.
+
 use std::collections::HashMap;
 
 use anyhow::Result;
 
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::{Solution, SolveError},
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Syntax Scoring";
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<Answer> {
+        part1(lines)
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<Answer> {
+        part2(lines)
+    }
+}
+
 /// Checks for lines containing an invalid sequence of open/close parentheses,
 /// e.g. "[{})", excluding lines that are incomplete but otherwise valid, e.g.
 /// "[{}", and computes a score based on the first invalid character in each
 /// line.
-pub fn part1(input: &str) -> Result<String> {
+pub fn part1(lines: &[String]) -> Result<Answer> {
     let mut close_scores = HashMap::new();
     close_scores.insert(')', 3);
     close_scores.insert(']', 57);
@@ -14,7 +43,7 @@ pub fn part1(input: &str) -> Result<String> {
     close_scores.insert('>', 25137);
 
     let mut score = 0;
-    for line in input.trim().lines() {
+    for line in lines {
         let mut stack = String::new();
         for c in line.chars() {
             if close_scores.contains_key(&c) {
@@ -32,7 +61,7 @@ pub fn part1(input: &str) -> Result<String> {
         }
     }
 
-    Ok(score.to_string())
+    Ok(score.into())
 }
 
 fn close_char(open: char) -> char {
@@ -49,7 +78,7 @@ fn close_char(open: char) -> char {
 /// otherwise valid, e.g. "[{}", and calculates a score for each such line based
 /// on the characters that would need to be added to complete the line. Then
 /// returns the median of all those scores.
-pub fn part2(input: &str) -> Result<String> {
+pub fn part2(lines: &[String]) -> Result<Answer> {
     let mut close_scores: HashMap<_, i64> = HashMap::new();
     close_scores.insert(')', 1);
     close_scores.insert(']', 2);
@@ -57,7 +86,7 @@ pub fn part2(input: &str) -> Result<String> {
     close_scores.insert('>', 4);
 
     let mut all_scores = vec![];
-    for line in input.trim().lines() {
+    for line in lines {
         // stack contains the minimum sequence of chars necessary to close all
         // sequences in the line (in reverse order).
         let mut stack = String::new();
@@ -88,15 +117,37 @@ pub fn part2(input: &str) -> Result<String> {
         all_scores.push(score);
     }
 
+    if all_scores.is_empty() {
+        return Err(SolveError::TooFew {
+            what: "incomplete lines",
+            needed: 1,
+            found: 0,
+        }
+        .into());
+    }
     all_scores.sort_unstable();
     let mid_score = all_scores[all_scores.len() / 2];
 
-    Ok(mid_score.to_string())
+    Ok(mid_score.into())
+}
+
+/// Parses the navigation subsystem, one line of brackets per line.
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let src = Source::new(Day10::DAY, input);
+    input
+        .trim()
+        .lines()
+        .map(|line| match line.char_indices().find(|&(_, c)| !"()[]{}<>".contains(c)) {
+            Some((i, c)) => Err(src.error(&line[i..i + c.len_utf8()], "a bracket")),
+            None => Ok(line.to_string()),
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::{parse_input, part1, part2};
+    use crate::solution::SolveError;
 
     const TEST_INPUT: &str = "
 [({(<(())[]>[[{[]{<()<>>
@@ -113,11 +164,29 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT).unwrap(), "26397");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "26397");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT).unwrap(), "288957");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "288957");
+    }
+
+    #[test]
+    fn test_no_incomplete_lines() {
+        let input = parse_input("(]\n[]\n").unwrap();
+        assert_eq!(part2(&input).unwrap(), "0");
+        let input = parse_input("(]\n{()()()>\n").unwrap();
+        let err = part2(&input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&SolveError::TooFew {
+                what: "incomplete lines",
+                needed: 1,
+                found: 0,
+            })
+        );
     }
 }
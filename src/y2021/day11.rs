@@ -0,0 +1,119 @@
+//! Day 11: Dumbo Octopus, <https://adventofcode.com/2021/day/11>.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    grid::{Grid, Neighbors},
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Dumbo Octopus";
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Grid<u32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(grid: &Grid<u32>) -> Result<Answer> {
+        part1(grid)
+    }
+
+    fn part2(grid: &Grid<u32>) -> Result<Answer> {
+        part2(grid)
+    }
+}
+
+const FLASH_VALUE: u32 = 10;
+
+/// Computes the total number of flashes (times a square exceeds 9) in the first
+/// 100 steps.
+pub fn part1(grid: &Grid<u32>) -> Result<Answer> {
+    let mut grid = grid.clone();
+    let flashes: usize = (0..100).map(|_| step(&mut grid)).sum();
+    Ok(flashes.into())
+}
+
+/// Computes the number of the first step on which all the squares will flash.
+pub fn part2(grid: &Grid<u32>) -> Result<Answer> {
+    let mut grid = grid.clone();
+    let mut step_number = 0;
+    loop {
+        step_number += 1;
+        let flashes = step(&mut grid);
+        if flashes == grid.len() {
+            return Ok(step_number.into());
+        }
+    }
+}
+
+/// Parses the energy level of each octopus.
+pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse_digits(&Source::new(Day11::DAY, input), input.trim())
+}
+
+/// Advances every octopus by a step, returning how many flashed.
+fn step(grid: &mut Grid<u32>) -> usize {
+    for (_, num) in grid.iter_mut() {
+        *num += 1;
+    }
+    let mut flashed = HashSet::new();
+    loop {
+        let mut updated = false;
+        for pos in grid.positions() {
+            if grid[pos] < FLASH_VALUE {
+                continue;
+            }
+            updated = true;
+            flashed.insert(pos);
+            grid[pos] = 0;
+            for neighbor in grid.neighbors(pos, Neighbors::Eight) {
+                if !flashed.contains(&neighbor) {
+                    grid[neighbor] += 1;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+    flashed.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "1656");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "195");
+    }
+}
@@ -1,11 +1,40 @@
+//! Day 12: Passage Pathing, <https://adventofcode.com/2021/day/12>.
+
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
-pub fn part1(input: &str) -> Result<String> {
-    let graph = parse_input(input);
-    let count = count_paths_part1(&graph, "start", "end", &mut HashSet::new());
-    Ok(count.to_string())
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 12;
+    const TITLE: &'static str = "Passage Pathing";
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Graph> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(graph: &Graph) -> Result<Answer> {
+        part1(graph)
+    }
+
+    fn part2(graph: &Graph) -> Result<Answer> {
+        part2(graph)
+    }
+}
+
+/// Counts the paths from start to end that visit each small cave at most once.
+pub fn part1(graph: &Graph) -> Result<Answer> {
+    let count = count_paths_part1(graph, "start", "end", &mut HashSet::new());
+    Ok(count.into())
 }
 
 fn count_paths_part1(
@@ -41,10 +70,11 @@ fn can_revisit_part1(node: &str) -> bool {
     is_uppercase(node)
 }
 
-pub fn part2(input: &str) -> Result<String> {
-    let graph = parse_input(input);
-    let count = count_paths_part2(&graph, "start", "end", &mut HashSet::new(), false);
-    Ok(count.to_string())
+/// Counts the paths from start to end that visit a single small cave at most
+/// twice and the others at most once.
+pub fn part2(graph: &Graph) -> Result<Answer> {
+    let count = count_paths_part2(graph, "start", "end", &mut HashSet::new(), false);
+    Ok(count.into())
 }
 
 fn count_paths_part2(
@@ -95,18 +125,23 @@ fn is_uppercase(s: &str) -> bool {
     s.chars().all(|c| c.is_uppercase())
 }
 
-fn parse_input(input: &str) -> Graph {
+/// Parses the connections between caves, one like "start-A" per line.
+pub fn parse_input(input: &str) -> Result<Graph, ParseError> {
+    let src = Source::new(Day12::DAY, input);
     let mut graph = Graph {
         edges: HashMap::new(),
     };
     for line in input.trim().lines() {
-        let ends: Vec<_> = line.split('-').collect();
-        graph.add_edge(ends[0].to_string(), ends[1].to_string());
+        let (a, b) = line
+            .split_once('-')
+            .ok_or_else(|| src.error(line, "two caves joined by '-'"))?;
+        graph.add_edge(a.to_string(), b.to_string());
     }
-    graph
+    Ok(graph)
 }
 
-struct Graph {
+/// The caves, each with the caves it's connected to.
+pub struct Graph {
     edges: HashMap<String, HashSet<String>>,
 }
 
@@ -119,13 +154,13 @@ impl Graph {
     }
 
     fn neighbors(&self, src: &str) -> &HashSet<String> {
-        self.edges.get(&*src).expect("no such key")
+        self.edges.get(src).expect("no such key")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::{parse_input, part1, part2};
 
     const TEST_INPUT: &str = "
 fs-end
@@ -150,11 +185,13 @@ start-RW
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT).unwrap(), "226");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "226");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT).unwrap(), "3509");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "3509");
     }
 }
@@ -0,0 +1,235 @@
+//! Day 13: Transparent Origami, <https://adventofcode.com/2021/day/13>.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::{SolveError, Solution},
+};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 13;
+    const TITLE: &'static str = "Transparent Origami";
+    type Input = Manual;
+
+    fn parse(input: &str) -> Result<Manual> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(manual: &Manual) -> Result<Answer> {
+        part1(manual)
+    }
+
+    fn part2(manual: &Manual) -> Result<Answer> {
+        part2(manual)
+    }
+}
+
+/// Computes the number of de-duped points that will be visible after the first
+/// fold.
+pub fn part1(manual: &Manual) -> Result<Answer> {
+    let fold = manual.folds.first().ok_or(SolveError::TooFew {
+        what: "folds",
+        needed: 1,
+        found: 0,
+    })?;
+
+    let folded_points = manual
+        .points
+        .iter()
+        .map(|p| apply_fold(p, fold))
+        .collect::<Result<HashSet<Point>, _>>()?;
+
+    Ok(folded_points.len().into())
+}
+
+/// Returns the pattern that will be visible after all the folds have been done.
+pub fn part2(manual: &Manual) -> Result<Answer> {
+    let mut points: HashSet<Point> = HashSet::from_iter(manual.points.iter().cloned());
+    for fold in manual.folds.iter() {
+        points = points
+            .iter()
+            .map(|p| apply_fold(p, fold))
+            .collect::<Result<_, _>>()?;
+    }
+
+    Ok(Answer::grid(
+        points.iter().map(|p| (p.x as usize, p.y as usize)),
+    ))
+}
+
+fn apply_fold(point: &Point, fold: &Fold) -> Result<Point, SolveError> {
+    // Reflects a coordinate past the fold line back over it.
+    let reflect = |coord: u32, i: u32| {
+        if coord > i {
+            i.checked_sub(coord - i).ok_or(SolveError::OffPaper {
+                x: point.x,
+                y: point.y,
+            })
+        } else {
+            Ok(coord)
+        }
+    };
+    Ok(match *fold {
+        Fold::Vertical(i) => Point {
+            x: reflect(point.x, i)?,
+            y: point.y,
+        },
+        Fold::Horizontal(i) => Point {
+            x: point.x,
+            y: reflect(point.y, i)?,
+        },
+    })
+}
+
+/// The dots on the transparent paper, and the folds to make in order.
+#[derive(Debug)]
+pub struct Manual {
+    points: Vec<Point>,
+    folds: Vec<Fold>,
+}
+
+#[derive(Debug)]
+enum Fold {
+    Vertical(u32),
+    Horizontal(u32),
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Point {
+    // y before x so points are sorted by y first.
+    y: u32,
+    x: u32,
+}
+
+/// Parses the dots on the transparent paper followed by the fold instructions.
+pub fn parse_input(input: &str) -> Result<Manual, ParseError> {
+    let src = Source::new(Day13::DAY, input);
+    let input = input.trim();
+    let (points, folds) = input
+        .split_once("\n\n")
+        .ok_or_else(|| src.error_after(input, "a blank line followed by folds"))?;
+
+    let points = points
+        .lines()
+        .map(|line| {
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| src.error(line, "a point like \"6,10\""))?;
+            Ok(Point {
+                x: src.number(x)?,
+                y: src.number(y)?,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let folds = folds
+        .lines()
+        .map(|line| {
+            let (axis, idx) = line
+                .strip_prefix("fold along ")
+                .and_then(|fold| fold.split_once('='))
+                .ok_or_else(|| src.error(line, "a fold like \"fold along y=7\""))?;
+            let idx = src.number(idx)?;
+            match axis {
+                "x" => Ok(Fold::Vertical(idx)),
+                "y" => Ok(Fold::Horizontal(idx)),
+                _ => Err(src.error(axis, "x or y")),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Manual { points, folds })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2, Manual};
+    use crate::solution::SolveError;
+
+    const TEST_INPUT: &str = "
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "17");
+    }
+
+    #[test]
+    fn test_part2() {
+        let expected_output = "\
+#####
+#...#
+#...#
+#...#
+#####";
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), expected_output);
+    }
+
+    #[test]
+    fn test_no_folds() {
+        let manual = Manual {
+            points: vec![],
+            folds: vec![],
+        };
+        let err = part1(&manual).unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&SolveError::TooFew {
+                what: "folds",
+                needed: 1,
+                found: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_fold_off_paper() {
+        let input = parse_input("10,0\n\nfold along x=2\n").unwrap();
+        let err = part1(&input).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&SolveError::OffPaper { x: 10, y: 0 }));
+        let input = parse_input("0,5\n\nfold along x=2\nfold along y=1\n").unwrap();
+        let err = part2(&input).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&SolveError::OffPaper { x: 0, y: 5 }));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("6,10\n\nfold along z=7\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 12));
+        assert_eq!(err.found, "z");
+        assert_eq!(err.expected, "x or y");
+        let err = parse_input("6,10\n0,14\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.found, "");
+    }
+}
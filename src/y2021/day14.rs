@@ -0,0 +1,229 @@
+//! Day 14: Extended Polymerization, <https://adventofcode.com/2021/day/14>.
+
+use std::collections::{
+    hash_map::{Iter, Values},
+    HashMap,
+};
+
+use anyhow::Result;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::{Metadata, Solution},
+};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 14;
+    const TITLE: &'static str = "Extended Polymerization";
+    type Input = Polymer;
+
+    fn metadata() -> Metadata {
+        Metadata {
+            notes: Some("Memoized recursive expansion of each pair of elements"),
+        }
+    }
+
+    fn parse(input: &str) -> Result<Polymer> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(polymer: &Polymer) -> Result<Answer> {
+        part1(polymer)
+    }
+
+    fn part2(polymer: &Polymer) -> Result<Answer> {
+        part2(polymer)
+    }
+}
+
+/// The polymer template and the pair insertion rules.
+#[derive(Debug)]
+pub struct Polymer {
+    template: String,
+    rules: InsertionRules,
+}
+
+/// Computes the most common element's count minus the least common's after 10
+/// steps of insertion.
+pub fn part1(polymer: &Polymer) -> Result<Answer> {
+    Ok(counts_after_iterations(polymer, 10).into())
+}
+
+/// Computes the most common element's count minus the least common's after 40
+/// steps of insertion.
+pub fn part2(polymer: &Polymer) -> Result<Answer> {
+    Ok(counts_after_iterations(polymer, 40).into())
+}
+
+fn counts_after_iterations(polymer: &Polymer, iters: usize) -> usize {
+    let Polymer { template, rules } = polymer;
+    let mut cache = Cache::new();
+
+    // A one-element template has no pairs to expand, leaving just its element.
+    let mut counts = template
+        .chars()
+        .tuple_windows()
+        .map(|(c1, c2)| compute(c1, c2, iters, rules, &mut cache))
+        .fold(Counter::new(), |mut total, counts| {
+            total.merge(counts);
+            total
+        });
+
+    template.chars().for_each(|c| counts.incr(c));
+    debug!(iters, entries = cache.len(), "expansion cache size");
+
+    counts
+        .values()
+        .minmax()
+        .into_option()
+        .map_or(0, |(min, max)| max - min)
+}
+
+type Cache = HashMap<(char, char, usize), Counter>;
+
+/// Recursively expand the sequence (c1, c2) following the specified expansion
+/// `rules` for `iters` iterations, and return a Counter representing the
+/// character counts in the resulting expanded sequence.
+fn compute(c1: char, c2: char, iters: usize, rules: &InsertionRules, cache: &mut Cache) -> Counter {
+    if iters == 0 {
+        return Counter::new();
+    }
+    let cache_key = (c1, c2, iters);
+    if let Some(val) = cache.get(&cache_key) {
+        return val.clone();
+    }
+
+    match rules.get(&(c1, c2)) {
+        Some(&new) => {
+            let mut res = Counter::new();
+            res.incr(new);
+            res.merge(compute(c1, new, iters - 1, rules, cache));
+            res.merge(compute(new, c2, iters - 1, rules, cache));
+            cache.insert(cache_key, res.clone());
+            res
+        }
+        None => Counter::new(),
+    }
+}
+
+#[derive(Clone)]
+struct Counter(HashMap<char, usize>);
+
+impl Counter {
+    fn new() -> Counter {
+        Counter(HashMap::new())
+    }
+
+    fn incr(&mut self, item: char) {
+        self.incr_by(item, 1);
+    }
+
+    fn incr_by(&mut self, item: char, amount: usize) {
+        let count = self.0.entry(item).or_insert(0);
+        *count += amount;
+    }
+
+    fn iter(&self) -> Iter<'_, char, usize> {
+        self.0.iter()
+    }
+
+    fn values(&self) -> Values<'_, char, usize> {
+        self.0.values()
+    }
+
+    fn merge(&mut self, other: Counter) {
+        for (&c, &amount) in other.iter() {
+            self.incr_by(c, amount);
+        }
+    }
+}
+
+type InsertionRules = HashMap<(char, char), char>;
+
+/// Parses the polymer template followed by the pair insertion rules.
+pub fn parse_input(input: &str) -> Result<Polymer, ParseError> {
+    let src = Source::new(Day14::DAY, input);
+    let input = input.trim();
+    let (template, rules) = input
+        .split_once("\n\n")
+        .ok_or_else(|| src.error_after(input, "a blank line followed by insertion rules"))?;
+
+    let rules = rules
+        .lines()
+        .map(|line| {
+            let rule = line.split_once(" -> ").and_then(|(pair, insert)| {
+                let mut pair = pair.chars();
+                let mut insert = insert.chars();
+                match (pair.next(), pair.next(), pair.next()) {
+                    (Some(a), Some(b), None) => match (insert.next(), insert.next()) {
+                        (Some(c), None) => Some(((a, b), c)),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            });
+            rule.ok_or_else(|| src.error(line, "a rule like \"CH -> B\""))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Polymer {
+        template: template.to_string(),
+        rules,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "
+NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "1588");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "2188189693529");
+    }
+
+    #[test]
+    fn test_one_element_template() {
+        let input = parse_input("N\n\nNN -> C\n").unwrap();
+        assert_eq!(part1(&input).unwrap(), "0");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("NNCB\n\nCH -> B\nHH ->\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.found, "HH ->");
+    }
+}
@@ -0,0 +1,161 @@
+//! Day 15: Chiton, <https://adventofcode.com/2021/day/15>.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    grid::{Grid, Neighbors, Pos},
+    parse::{ParseError, Source},
+    solution::{Metadata, Solution},
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 15;
+    const TITLE: &'static str = "Chiton";
+    type Input = Grid<u32>;
+
+    fn metadata() -> Metadata {
+        Metadata {
+            notes: Some("Dijkstra's algorithm over the (tiled) grid"),
+        }
+    }
+
+    fn parse(input: &str) -> Result<Grid<u32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(grid: &Grid<u32>) -> Result<Answer> {
+        part1(grid)
+    }
+
+    fn part2(grid: &Grid<u32>) -> Result<Answer> {
+        part2(grid)
+    }
+}
+
+/// Computes the lowest total risk of any path through the cave.
+pub fn part1(grid: &Grid<u32>) -> Result<Answer> {
+    Ok(shortest_distance(grid)?.into())
+}
+
+/// Computes the lowest total risk of any path through the full cave, which is
+/// the map tiled five times in each direction.
+pub fn part2(grid: &Grid<u32>) -> Result<Answer> {
+    // Create a 5x5 grid of the original grid, with each new section of grid
+    // having its digits increased by the distance from the original, wrapping
+    // around from 9 to 1.
+    let tiled = grid.tiled(5, 5, |&risk, (x, y)| (risk - 1 + (x + y) as u32) % 9 + 1);
+    Ok(shortest_distance(&tiled)?.into())
+}
+
+/// Finds the lowest total risk of any path from the top left to the bottom
+/// right of a grid of single-digit risk levels, not counting the start.
+pub fn shortest_distance(grid: &Grid<u32>) -> Result<u32> {
+    if grid.is_empty() {
+        return Err(anyhow::anyhow!("no path through an empty grid"));
+    }
+    let end = (grid.width() - 1, grid.height() - 1);
+
+    let mut heap: BinaryHeap<HeapItem> = BinaryHeap::new();
+    heap.push(HeapItem {
+        distance: 0,
+        pos: (0, 0),
+    });
+
+    let mut distances: HashMap<Pos, u32> = HashMap::new();
+
+    while let Some(item) = heap.pop() {
+        if item.pos == end {
+            return Ok(item.distance);
+        }
+
+        for neighbor in grid.neighbors(item.pos, Neighbors::Four) {
+            let distance = item.distance + grid[neighbor];
+            if let Some(&min_distance) = distances.get(&neighbor) {
+                if distance >= min_distance {
+                    // Already found a shorter route to this neighbor, skip it.
+                    continue;
+                }
+            }
+            distances.insert(neighbor, distance);
+            heap.push(HeapItem {
+                distance,
+                pos: neighbor,
+            });
+        }
+    }
+
+    Err(anyhow::anyhow!("no path through the grid"))
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct HeapItem {
+    distance: u32,
+    pos: Pos,
+}
+
+impl Ord for HeapItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.cmp(&other.distance).reverse()
+    }
+}
+
+impl PartialOrd for HeapItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parses the risk level of each position in the cave, which is from 1 to 9.
+pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    let src = Source::new(Day15::DAY, input);
+    Grid::parse(&src, input.trim(), "a risk level from 1 to 9", |c| {
+        c.to_digit(10).filter(|&risk| risk > 0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2};
+
+    const TEST_INPUT: &str = "
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "40");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), "315");
+    }
+
+    #[test]
+    fn test_parse_rejects_zero_risk() {
+        let err = parse_input("19\n10\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.found, "0");
+        assert_eq!(err.expected, "a risk level from 1 to 9");
+    }
+}
@@ -0,0 +1,251 @@
+//! Day 16: Packet Decoder, <https://adventofcode.com/2021/day/16>.
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::{Solution, SolveError},
+};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 16;
+    const TITLE: &'static str = "Packet Decoder";
+    type Input = Packet;
+
+    fn parse(input: &str) -> Result<Packet> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(packet: &Packet) -> Result<Answer> {
+        part1(packet)
+    }
+
+    fn part2(packet: &Packet) -> Result<Answer> {
+        part2(packet)
+    }
+}
+
+/// Sums the version numbers of every packet in the transmission.
+pub fn part1(packet: &Packet) -> Result<Answer> {
+    Ok(version_sum(packet).into())
+}
+
+fn version_sum(packet: &Packet) -> u64 {
+    packet.version
+        + match &packet.content {
+            PacketContent::Operator(_, children) => children.iter().map(version_sum).sum(),
+            PacketContent::Literal(_) => 0,
+        }
+}
+
+/// Evaluates the expression that the transmission encodes.
+pub fn part2(packet: &Packet) -> Result<Answer> {
+    Ok(eval_packet(packet)?.into())
+}
+
+fn eval_packet(packet: &Packet) -> Result<u64, SolveError> {
+    match &packet.content {
+        PacketContent::Literal(val) => Ok(*val),
+        PacketContent::Operator(type_id, subpackets) => {
+            let subvals = subpackets
+                .iter()
+                .map(eval_packet)
+                .collect::<Result<Vec<_>, _>>()?;
+            let too_few = |needed| SolveError::TooFew {
+                what: "subpackets",
+                needed,
+                found: subvals.len(),
+            };
+            match (type_id, &subvals[..]) {
+                (0, _) => Ok(subvals.iter().sum()),
+                (1, _) => Ok(subvals.iter().product()),
+                (2, _) => subvals.iter().copied().min().ok_or_else(|| too_few(1)),
+                (3, _) => subvals.iter().copied().max().ok_or_else(|| too_few(1)),
+                (5, [a, b, ..]) => Ok((a > b) as u64),
+                (6, [a, b, ..]) => Ok((a < b) as u64),
+                (7, [_, _, ..]) => Ok(subvals.iter().all_equal() as u64),
+                (5..=7, _) => Err(too_few(2)),
+                _ => Err(SolveError::UnknownOperator(*type_id)),
+            }
+        }
+    }
+}
+
+/// The outermost packet of a transmission.
+#[derive(Debug)]
+pub struct Packet {
+    version: u64,
+    length: usize,
+    content: PacketContent,
+}
+
+#[derive(Debug)]
+enum PacketContent {
+    Operator(u64, Vec<Packet>),
+    Literal(u64),
+}
+
+/// Parses the hexadecimal transmission into its outermost packet.
+pub fn parse_input(input: &str) -> Result<Packet, ParseError> {
+    let src = Source::new(Day16::DAY, input);
+    let hex = input.trim();
+    if let Some((i, c)) = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(src.error(&hex[i..i + c.len_utf8()], "a hex digit"));
+    }
+    let bytes = hex::decode(hex).map_err(|_| src.error_after(hex, "another hex digit"))?;
+    parse_packet(
+        &mut bytes
+            .iter()
+            .flat_map(|&b| (0..8).rev().map(move |i| (b >> i) & 1)),
+    )
+    .ok_or_else(|| src.error_after(hex, "the rest of the packet"))
+}
+
+/// Returns `None` if the bits run out part way through the packet.
+fn parse_packet(bits: &mut impl Iterator<Item = u8>) -> Option<Packet> {
+    let version = parse_num(bits, 3)?;
+    let typ = parse_num(bits, 3)?;
+    let mut length = 6;
+    let packet = match typ {
+        // 4 indicates a literal integer.
+        4 => {
+            let mut val: u64 = 0;
+            loop {
+                let part = parse_num(bits, 5)?;
+                length += 5;
+                val <<= 4;
+                let mask = 0b10000;
+                if part & mask > 0 {
+                    val |= part & 0b1111;
+                } else {
+                    val |= part;
+                    break;
+                }
+            }
+
+            Packet {
+                version,
+                length,
+                content: PacketContent::Literal(val),
+            }
+        }
+        // Anything else indicates an operator.
+        _ => {
+            let length_type = parse_num(bits, 1)?;
+            length += 1;
+            let mut subpackets = vec![];
+            if length_type == 0 {
+                let subpackets_length = parse_num(bits, 15)? as usize;
+                length += 15;
+                let target_length = length + subpackets_length;
+                while length < target_length {
+                    let sp = parse_packet(bits)?;
+                    length += sp.length;
+                    subpackets.push(sp);
+                }
+            } else {
+                let subpacket_count = parse_num(bits, 11)?;
+                length += 11;
+                for _ in 0..subpacket_count {
+                    let sp = parse_packet(bits)?;
+                    length += sp.length;
+                    subpackets.push(sp);
+                }
+            }
+
+            Packet {
+                version,
+                length,
+                content: PacketContent::Operator(typ, subpackets),
+            }
+        }
+    };
+    Some(packet)
+}
+
+fn parse_num(bits: &mut impl Iterator<Item = u8>, len: usize) -> Option<u64> {
+    assert!(len <= 32, "can only parse up to 32 bits");
+    let bits = bits.take(len).collect_vec();
+    if bits.len() < len {
+        return None;
+    }
+    let mut ret = 0;
+    bits.iter().for_each(|&b| {
+        assert!(b <= 1, "bits must only be zero or one, got {}", b);
+        ret <<= 1;
+        ret += b as u64;
+    });
+    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, part1, part2, Packet, PacketContent};
+    use crate::solution::SolveError;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_input("D2FE28").unwrap()).unwrap(), "6");
+        assert_eq!(part1(&parse_input("8A004A801A8002F478").unwrap()).unwrap(), "16");
+        assert_eq!(part1(&parse_input("620080001611562C8802118E34").unwrap()).unwrap(), "12");
+        assert_eq!(part1(&parse_input("C0015000016115A2E0802F182340").unwrap()).unwrap(), "23");
+        assert_eq!(part1(&parse_input("A0016C880162017C3686B18A3D4780").unwrap()).unwrap(), "31");
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_input("C200B40A82").unwrap()).unwrap(), "3");
+        assert_eq!(part2(&parse_input("04005AC33890").unwrap()).unwrap(), "54");
+        assert_eq!(part2(&parse_input("880086C3E88112").unwrap()).unwrap(), "7");
+        assert_eq!(part2(&parse_input("F600BC2D8F").unwrap()).unwrap(), "0");
+        assert_eq!(part2(&parse_input("9C005AC2F8F0").unwrap()).unwrap(), "0");
+        assert_eq!(part2(&parse_input("9C0141080250320F1802104A08").unwrap()).unwrap(), "1");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("D2FE2G").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        assert_eq!(err.expected, "a hex digit");
+        let err = parse_input("D2FE").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.expected, "the rest of the packet");
+    }
+
+    fn operator(type_id: u64, values: &[u64]) -> Packet {
+        let subpackets = values
+            .iter()
+            .map(|&val| Packet {
+                version: 0,
+                length: 11,
+                content: PacketContent::Literal(val),
+            })
+            .collect();
+        Packet {
+            version: 0,
+            length: 0,
+            content: PacketContent::Operator(type_id, subpackets),
+        }
+    }
+
+    #[test]
+    fn test_degenerate_operators() {
+        let too_few = |needed, found| SolveError::TooFew {
+            what: "subpackets",
+            needed,
+            found,
+        };
+        let err = part2(&operator(2, &[])).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&too_few(1, 0)));
+        let err = part2(&operator(6, &[1])).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&too_few(2, 1)));
+        let err = part2(&operator(4, &[1])).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&SolveError::UnknownOperator(4)));
+        assert_eq!(part2(&operator(0, &[])).unwrap(), "0");
+    }
+}
@@ -1,8 +1,39 @@
+//! Day 17: Trick Shot, <https://adventofcode.com/2021/day/17>.
+
 use anyhow::{anyhow, Result};
-use regex::{Captures, Regex};
+use regex::Regex;
+
+use crate::{
+    answer::Answer,
+    parse::{ParseError, Source},
+    solution::Solution,
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: i32 = 2021;
+    const DAY: u32 = 17;
+    const TITLE: &'static str = "Trick Shot";
+    type Input = Region;
+
+    fn parse(input: &str) -> Result<Region> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(region: &Region) -> Result<Answer> {
+        part1(region)
+    }
+
+    fn part2(region: &Region) -> Result<Answer> {
+        part2(region)
+    }
+}
 
-pub fn part1(input: &str) -> Result<String> {
-    let mut region = parse_input(input)?;
+/// Computes the highest y position a probe can reach while still landing in
+/// the target area.
+pub fn part1(region: &Region) -> Result<Answer> {
+    let mut region = region.clone();
     if (region.min_x..=region.max_x).contains(&0) {
         return Err(anyhow!(
             "maximal y velocity is infinite as the region sits on the y axis"
@@ -39,48 +70,44 @@ pub fn part1(input: &str) -> Result<String> {
 
     let max_y_reached = sum_1_to(max_y_vel);
 
-    Ok(max_y_reached.to_string())
+    Ok(max_y_reached.into())
 }
 
 fn sum_1_to(n: i32) -> i32 {
     (n * (n + 1)) / 2
 }
 
-pub fn part2(_: &str) -> Result<String> {
-    Ok(String::new())
+/// Not solved yet.
+pub fn part2(_: &Region) -> Result<Answer> {
+    Ok(Answer::Unsolved)
 }
 
-fn parse_input(input: &str) -> Result<Region> {
-    let re_str = r"^target area: x=(?P<min_x>-?\d+)..(?P<max_x>-?\d+), y=(?P<min_y>-?\d+)..(?P<max_y>-d?\d+)$";
+/// Parses the target area, like "target area: x=20..30, y=-10..-5".
+pub fn parse_input(input: &str) -> Result<Region, ParseError> {
+    let src = Source::new(Day17::DAY, input);
+    let re_str = r"^target area: x=(?P<min_x>-?\d+)..(?P<max_x>-?\d+), y=(?P<min_y>-?\d+)..(?P<max_y>-?\d+)$";
     let input_re = Regex::new(re_str).expect("failed to compile input regex");
-    let caps = input_re
-        .captures(input.trim())
-        .ok_or(anyhow::anyhow!("input does not match regex"))?;
+    let line = input.trim();
+    let caps = input_re.captures(line).ok_or_else(|| {
+        src.error(line, "a target area like \"target area: x=20..30, y=-10..-5\"")
+    })?;
+    let num = |name| src.number(&caps[name]);
     let region = Region {
-        min_x: cap_group_to_int(&caps, "min_x")?,
-        max_x: cap_group_to_int(&caps, "max_x")?,
-        min_y: cap_group_to_int(&caps, "min_y")?,
-        max_y: cap_group_to_int(&caps, "max_y")?,
+        min_x: num("min_x")?,
+        max_x: num("max_x")?,
+        min_y: num("min_y")?,
+        max_y: num("max_y")?,
     };
     if region.min_x < region.max_x && region.min_y < region.max_y {
         Ok(region)
     } else {
-        Err(anyhow!("invalid region: {:?}", region))
-    }
-}
-
-fn cap_group_to_int(caps: &Captures, name: &str) -> Result<i32> {
-    let group = caps
-        .name(name)
-        .ok_or_else(|| anyhow!("no group with name {}", name))?;
-    match group.as_str().parse() {
-        Ok(val) => Ok(val),
-        Err(_) => Err(anyhow!("failed to parse group '{}' to int", group.as_str())),
+        Err(src.error(line, "ranges from lower to higher values"))
     }
 }
 
-#[derive(Debug)]
-struct Region {
+/// The target area.
+#[derive(Clone, Debug)]
+pub struct Region {
     min_x: i32,
     max_x: i32,
     min_y: i32,
@@ -89,17 +116,20 @@ struct Region {
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::{parse_input, part1, part2};
+    use crate::answer::Answer;
 
     const TEST_INPUT: &str = "target area: x=20..30, y=-10..-5";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT).unwrap(), "45");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part1(&input).unwrap(), "45");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT).unwrap(), "");
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input).unwrap(), Answer::Unsolved);
     }
 }
@@ -0,0 +1,47 @@
+use std::{
+    env, fs,
+    path::Path,
+    process::{self, Command, Output},
+};
+
+/// Runs the binary in `dir` with no session cookie and no config, so that
+/// nothing can be downloaded.
+fn run_offline(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc_2021"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("AOC_SESSION_ID")
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("AOC_CACHE_DIR", dir.join("cache"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_show_without_session() {
+    let dir = env::temp_dir().join(format!("aoc-cli-show-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let output = run_offline(&dir, &["--year", "2021", "show", "1"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("no cached puzzle for day 1"), "{}", stderr);
+
+    let account = dir.join("cache").join("2021").join("0123456789ab");
+    fs::create_dir_all(&account).unwrap();
+    fs::write(
+        account.join("day01.puzzle.html"),
+        "<article class=\"day-desc\"><h2>--- Day 1: Sonar Sweep ---</h2>\
+         <p>You're minding your own business.</p></article>\n",
+    )
+    .unwrap();
+    let output = run_offline(&dir, &["--year", "2021", "show", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("--- Day 1: Sonar Sweep ---"), "{}", stdout);
+    assert!(stdout.contains("You're minding your own business."));
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,46 @@
+use aoc_2021::{
+    solution::{years, Registry, Solution},
+    y2021::{day01, day06, day15, day16},
+};
+
+const DAY01_INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+const DAY15_INPUT: &str = "
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+#[test]
+fn test_solution_trait() {
+    let input = day01::Day01::parse(DAY01_INPUT).unwrap();
+    assert_eq!(day01::Day01::part1(&input).unwrap(), "7");
+    assert_eq!(day01::Day01::part2(&input).unwrap(), "5");
+}
+
+#[test]
+fn test_free_functions() {
+    let fish = day06::Day06::parse("3,4,3,1,2").unwrap();
+    assert_eq!(day06::part1(&fish).unwrap(), "5934");
+    let grid = day15::parse_input(DAY15_INPUT).unwrap();
+    assert_eq!(day15::shortest_distance(&grid).unwrap(), 40);
+    let packet = day16::Day16::parse("C200B40A82").unwrap();
+    assert_eq!(day16::part2(&packet).unwrap(), "3");
+}
+
+#[test]
+fn test_registry() {
+    assert!(years().contains(&2021));
+    let registry = Registry::new(2021);
+    assert_eq!(registry.iter().count(), 17);
+    assert_eq!(registry.lookup(15).unwrap().title(), "Chiton");
+    assert!(registry.lookup(26).is_err());
+    assert_eq!(registry.solve(15, 2, DAY15_INPUT).unwrap(), "315");
+}